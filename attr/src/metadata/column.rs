@@ -1,7 +1,7 @@
-use crate::{Ident, Type};
+use crate::{Ident, TableMeta, Type};
 use proc_macro2::TokenStream;
-use structmeta::{Flag, StructMeta};
-use syn::{Attribute, Field, LitStr, Path};
+use structmeta::{Flag, NameArgs, StructMeta};
+use syn::{Attribute, Field, LitInt, LitStr, Path};
 
 #[derive(Debug, Clone)]
 pub enum Join {
@@ -10,7 +10,12 @@ pub enum Join {
         column: String,
     },
     ManyToMany {
+        /// Name of the join table
         table: String,
+        /// Column on the join table that references this table. Inferred from the primary key if not set.
+        local_key: Option<String>,
+        /// Column on the join table that references the joined table. Inferred from the primary key if not set.
+        foreign_key: Option<String>,
     },
     OneToMany {
         model: String,
@@ -34,6 +39,28 @@ pub struct ColumnMeta {
     pub rust_default: Option<String>,
     pub join: Option<Join>,
     pub json: bool,
+    /// Set to the current time on insert.
+    pub created_at: bool,
+    /// Set to the current time on insert and update.
+    pub updated_at: bool,
+    /// Checked and incremented by updates, for optimistic locking.
+    pub version: bool,
+    /// Checks on the value before it's written, set by `#[ormlite(validate(...))]`.
+    pub validation: Validation,
+}
+
+/// Checks on a column's value, from `#[ormlite(validate(...))]`.
+#[derive(Clone, Debug, Default)]
+pub struct Validation {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub regex: Option<String>,
+}
+
+impl Validation {
+    pub fn is_empty(&self) -> bool {
+        self.min_length.is_none() && self.max_length.is_none() && self.regex.is_none()
+    }
 }
 
 impl ColumnMeta {
@@ -59,11 +86,15 @@ impl ColumnMeta {
             rust_default: None,
             join: None,
             json: false,
+            created_at: false,
+            updated_at: false,
+            version: false,
+            validation: Validation::default(),
         }
     }
 
     pub fn is_join(&self) -> bool {
-        matches!(self.ty, Type::Join(_))
+        self.ty.is_join()
     }
 
     pub fn is_join_one(&self) -> bool {
@@ -73,11 +104,38 @@ impl ColumnMeta {
         matches!(join, Join::ManyToOne { .. })
     }
 
+    /// Whether the field is a join that isn't backed by a column on this table, i.e. one to many or many to many.
     pub fn is_join_many(&self) -> bool {
         let Some(join) = &self.join else {
             return false;
         };
-        matches!(join, Join::ManyToOne { .. } | Join::ManyToMany { .. })
+        matches!(join, Join::OneToMany { .. } | Join::ManyToMany { .. })
+    }
+
+    /// For a many to many join, the join table and its columns that reference the local and the joined table.
+    pub fn join_table_columns(&self, local: &TableMeta, joined: &TableMeta) -> Option<(&str, String, String)> {
+        let Some(Join::ManyToMany {
+            table,
+            local_key,
+            foreign_key,
+        }) = &self.join
+        else {
+            return None;
+        };
+        let local_key = local_key.clone().unwrap_or_else(|| local.join_table_key());
+        let foreign_key = foreign_key.clone().unwrap_or_else(|| joined.join_table_key());
+        if local_key == foreign_key {
+            panic!(
+                "Join table {table} for {} would use {local_key} for both sides of the join. Set local_key and foreign_key on the field.",
+                self.ident
+            );
+        }
+        Some((table, local_key, foreign_key))
+    }
+
+    /// Whether ormlite sets the column to the current time, instead of binding the field.
+    pub fn is_timestamp(&self) -> bool {
+        self.created_at || self.updated_at
     }
 
     pub fn is_option(&self) -> bool {
@@ -90,9 +148,7 @@ impl ColumnMeta {
 
     /// We expect this to only return a `Model` of some kind.
     pub fn joined_struct_name(&self) -> Option<String> {
-        let Type::Join(join) = &self.ty else {
-            return None;
-        };
+        let join = self.ty.joined_type()?;
         Some(join.inner_type_name())
     }
 
@@ -104,6 +160,7 @@ impl ColumnMeta {
         let ident = f.ident.as_ref().expect("No ident on field");
         let attrs = ColumnAttr::from_attrs(&f.attrs);
         let mut column = ColumnMeta::from_syn(ident, &f.ty);
+        let mut join_table_keys = (None, None);
         for attr in attrs {
             if attr.primary_key.value() {
                 column.marked_primary_key = true;
@@ -118,8 +175,16 @@ impl ColumnMeta {
             if let Some(table_name) = attr.join_table {
                 column.join = Some(Join::ManyToMany {
                     table: table_name.value(),
+                    local_key: None,
+                    foreign_key: None,
                 });
             }
+            if let Some(local_key) = attr.local_key {
+                join_table_keys.0 = Some(local_key.value());
+            }
+            if let Some(foreign_key) = attr.foreign_key {
+                join_table_keys.1 = Some(foreign_key.value());
+            }
             if let Some(path) = attr.foreign_field {
                 let mut segments = path.segments.iter();
                 let model = segments
@@ -141,6 +206,35 @@ impl ColumnMeta {
             column.marked_primary_key |= attr.insertable_primary_key.value();
             column.skip |= attr.skip.value();
             column.json |= attr.json.value();
+            column.created_at |= attr.created_at.value();
+            column.updated_at |= attr.updated_at.value();
+            column.version |= attr.version.value();
+            if let Some(validate) = attr.validate {
+                let validate = validate.args;
+                if let Some(length) = validate.length {
+                    let length = length.args;
+                    column.validation.min_length = length.min.map(|n| n.base10_parse().unwrap());
+                    column.validation.max_length = length.max.map(|n| n.base10_parse().unwrap());
+                }
+                if let Some(regex) = validate.regex {
+                    column.validation.regex = Some(regex.value());
+                }
+            }
+        }
+        if column.created_at && column.updated_at {
+            panic!("Column {ident} can't be both created_at and updated_at");
+        }
+        // The timestamps are always set by the database, so they aren't part of the insert struct.
+        column.has_database_default |= column.is_timestamp();
+        // The version starts at the database default, and only updates change it.
+        column.has_database_default |= column.version;
+        if let Some(Join::ManyToMany {
+            local_key, foreign_key, ..
+        }) = &mut column.join
+        {
+            (*local_key, *foreign_key) = join_table_keys;
+        } else if join_table_keys.0.is_some() || join_table_keys.1.is_some() {
+            panic!("Column {ident} sets local_key or foreign_key, which are only used with join_table (many to many)");
         }
         if column.ty.is_join() ^ column.join.is_some() {
             panic!("Column {ident} is a Join. You must specify one of these attributes: column (many to one), join_table (many to many), or foreign_field (one to many)");
@@ -160,6 +254,10 @@ impl ColumnMeta {
             rust_default: None,
             join: None,
             json: false,
+            created_at: false,
+            updated_at: false,
+            version: false,
+            validation: Validation::default(),
         }
     }
 
@@ -175,6 +273,10 @@ impl ColumnMeta {
             rust_default: None,
             join: None,
             json: false,
+            created_at: false,
+            updated_at: false,
+            version: false,
+            validation: Validation::default(),
         }
     }
 }
@@ -204,6 +306,14 @@ pub struct ColumnAttr {
     /// }
     pub join_table: Option<LitStr>,
 
+    /// The column on the join table that references this model. Only used with `join_table`.
+    /// Defaults to the table name and its primary key, e.g. `user_id`.
+    pub local_key: Option<LitStr>,
+
+    /// The column on the join table that references the joined model. Only used with `join_table`.
+    /// Defaults to the joined table name and its primary key, e.g. `role_id`.
+    pub foreign_key: Option<LitStr>,
+
     /// Example:
     /// pub struct User {
     ///     pub id: i32,
@@ -231,6 +341,53 @@ pub struct ColumnAttr {
     pub skip: Flag,
 
     pub json: Flag,
+
+    /// Set the column to the current time when the model is inserted. The migration gives it a default of the
+    /// current time.
+    /// Example:
+    /// pub struct User {
+    ///     #[ormlite(created_at)]
+    ///     pub created_at: DateTime<Utc>,
+    /// }
+    pub created_at: Flag,
+
+    /// Set the column to the current time when the model is inserted or updated, including by `update_partial`
+    /// and `update_where`, unless the update sets it.
+    pub updated_at: Flag,
+
+    /// Use the column, an integer, for optimistic locking. Updates only apply if the column still has the model's
+    /// value, and increment it. Otherwise they fail with `Error::StaleData`. The migration gives it a default of 0.
+    /// Example:
+    /// pub struct User {
+    ///     #[ormlite(version)]
+    ///     pub version: i32,
+    /// }
+    pub version: Flag,
+
+    /// Check the value before inserting or updating it, and when building it with a `ModelBuilder`. Invalid values
+    /// fail with `Error::Validation`. A maximum length also makes a `String` column `VARCHAR(n)` in migrations.
+    /// `None` values of optional columns are valid.
+    /// Example:
+    /// pub struct User {
+    ///     #[ormlite(validate(length(min = 3, max = 255), regex = "^[^@]+@[^@]+$"))]
+    ///     pub email: String,
+    /// }
+    pub validate: Option<NameArgs<ValidateAttr>>,
+}
+
+/// Arguments of `#[ormlite(validate(...))]`.
+#[derive(StructMeta)]
+pub struct ValidateAttr {
+    /// The number of characters of a string, or elements of a `Vec`.
+    pub length: Option<NameArgs<LengthAttr>>,
+    /// A pattern that string values must match.
+    pub regex: Option<LitStr>,
+}
+
+#[derive(StructMeta)]
+pub struct LengthAttr {
+    pub min: Option<LitInt>,
+    pub max: Option<LitInt>,
 }
 
 impl ColumnAttr {
@@ -282,10 +439,95 @@ pub name: String
         assert!(args.default.value());
     }
 
+    #[test]
+    fn test_timestamps() {
+        let item: ItemStruct = syn::parse_str(
+            r#"
+struct Foo {
+#[ormlite(created_at)]
+pub created_at: DateTime<Utc>,
+#[ormlite(updated_at)]
+pub updated_at: DateTime<Utc>,
+pub name: String,
+}
+"#,
+        )
+        .unwrap();
+        let Fields::Named(fields) = item.fields else {
+            panic!();
+        };
+        let columns = ColumnMeta::from_fields(fields.named.iter());
+        assert!(columns[0].created_at && !columns[0].updated_at);
+        assert!(columns[1].updated_at && !columns[1].created_at);
+        assert!(columns[0].has_database_default && columns[1].has_database_default);
+        assert!(!columns[2].is_timestamp());
+    }
+
+    #[test]
+    fn test_validate() {
+        let item: ItemStruct = syn::parse_str(
+            r#"
+struct Foo {
+#[ormlite(validate(length(max = 255), regex = "^[a-z]+$"))]
+pub name: String,
+}
+"#,
+        )
+        .unwrap();
+        let Fields::Named(fields) = item.fields else {
+            panic!();
+        };
+        let columns = ColumnMeta::from_fields(fields.named.iter());
+        let validation = &columns[0].validation;
+        assert_eq!((validation.min_length, validation.max_length), (None, Some(255)));
+        assert_eq!(validation.regex.as_deref(), Some("^[a-z]+$"));
+    }
+
+    #[test]
+    fn test_version() {
+        let attr: Attribute = parse_quote!(#[ormlite(version)]);
+        let args: ColumnAttr = attr.parse_args().unwrap();
+        assert!(args.version.value());
+    }
+
     #[test]
     fn test_column() {
         let attr: Attribute = parse_quote!(#[ormlite(column = "org_id")]);
         let args: ColumnAttr = attr.parse_args().unwrap();
         assert!(args.column.is_some());
     }
+
+    #[test]
+    fn test_join_table_columns() {
+        let item: ItemStruct = syn::parse_str(
+            r#"
+struct User {
+id: i32,
+#[ormlite(join_table = "user_role")]
+roles: Join<Vec<Role>>,
+#[ormlite(join_table = "follow", local_key = "follower_id", foreign_key = "followee_id")]
+following: Join<Vec<User>>,
+}
+"#,
+        )
+        .unwrap();
+        let Fields::Named(fields) = item.fields else {
+            panic!();
+        };
+        let columns = ColumnMeta::from_fields(fields.named.iter());
+        let mut user = TableMeta::mock("user", columns.clone());
+        user.pkeys = vec!["id".to_string()];
+        let mut role = TableMeta::mock("role", vec![ColumnMeta::mock("role_id", "i32")]);
+        role.pkeys = vec!["role_id".to_string()];
+
+        let (table, local, foreign) = columns[1].join_table_columns(&user, &role).unwrap();
+        assert_eq!(table, "user_role");
+        assert_eq!(local, "user_id");
+        assert_eq!(foreign, "role_id");
+
+        let (table, local, foreign) = columns[2].join_table_columns(&user, &user).unwrap();
+        assert_eq!(table, "follow");
+        assert_eq!(local, "follower_id");
+        assert_eq!(foreign, "followee_id");
+    }
 }
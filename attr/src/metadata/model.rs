@@ -10,7 +10,8 @@ pub struct ModelMeta {
     pub table: TableMeta,
     pub insert_struct: Option<Ident>,
     pub extra_derives: Option<Vec<Ident>>,
-    pub pkey: ColumnMeta,
+    /// The primary key columns. Several columns make a composite primary key.
+    pub pkeys: Vec<ColumnMeta>,
 }
 
 impl ModelMeta {
@@ -18,20 +19,35 @@ impl ModelMeta {
         Ident::from(format!("{}Builder", self.ident.as_ref()))
     }
 
+    pub fn columns_struct(&self) -> Ident {
+        Ident::from(format!("{}Columns", self.ident.as_ref()))
+    }
+
     pub fn database_columns_except_pkey(&self) -> impl Iterator<Item = &ColumnMeta> + '_ {
-        self.columns
-            .iter()
-            .filter(|&c| !c.skip)
-            .filter(|&c| self.pkey.name != c.name)
+        self.database_columns().filter(|&c| !self.is_pkey(c))
+    }
+
+    pub fn is_pkey(&self, column: &ColumnMeta) -> bool {
+        self.pkeys.iter().any(|p| p.name == column.name)
+    }
+
+    /// The primary key column, for the many places (e.g. joins) that need a single column primary key.
+    pub fn pkey(&self) -> &ColumnMeta {
+        match self.pkeys.as_slice() {
+            [pkey] => pkey,
+            _ => panic!("{} has a composite primary key, which joins don't support", self.ident),
+        }
     }
 
     pub fn from_derive(ast: &DeriveInput) -> Self {
         let attrs = TableAttr::from_attrs(&ast.attrs);
         let table = TableMeta::new(ast, &attrs);
-        let pkey = table.pkey.as_deref().expect(&format!(
-            "No column marked with #[ormlite(primary_key)], and no column named id, uuid, {0}_id, or {0}_uuid",
-            table.name,
-        ));
+        if table.pkeys.is_empty() {
+            panic!(
+                "No column marked with #[ormlite(primary_key)], and no column named id, uuid, {0}_id, or {0}_uuid",
+                table.name,
+            );
+        }
         let mut insert_struct = None;
         let mut extra_derives: Option<Vec<syn::Ident>> = None;
         for attr in attrs {
@@ -47,7 +63,11 @@ impl ModelMeta {
                 }
             }
         }
-        let pkey = table.columns.iter().find(|&c| c.name == pkey).unwrap().clone();
+        let pkeys = table
+            .pkeys
+            .iter()
+            .map(|pkey| table.columns.iter().find(|&c| &c.name == pkey).unwrap().clone())
+            .collect();
         let insert_struct = insert_struct.map(|v| Ident::from(v));
         let extra_derives = extra_derives.take().map(|vec| vec.into_iter().map(|v| v.to_string()).map(Ident::from).collect());
         
@@ -55,7 +75,7 @@ impl ModelMeta {
             table,
             insert_struct,
             extra_derives, 
-            pkey,
+            pkeys,
         }
     }
 
@@ -63,7 +83,7 @@ impl ModelMeta {
     pub fn mock(name: &str, columns: Vec<ColumnMeta>) -> Self {
         let inner = TableMeta::mock(name, columns);
         Self {
-            pkey: inner.columns.iter().find(|c| c.name == "id").unwrap().clone(),
+            pkeys: inner.columns.iter().filter(|c| c.name == "id").cloned().collect(),
             table: inner,
             extra_derives: None,
             insert_struct: None,
@@ -95,6 +115,41 @@ mod tests {
         .unwrap();
         let input = DeriveInput::from(ast);
         let meta = ModelMeta::from_derive(&input);
-        assert_eq!(meta.pkey.name, "Id");
+        assert_eq!(meta.pkey().name, "Id");
+    }
+
+    #[test]
+    fn test_composite_primary_key() {
+        let ast = syn::parse_str::<ItemStruct>(
+            r#"struct UserRole {
+            #[ormlite(primary_key)]
+            user_id: i32,
+            #[ormlite(primary_key)]
+            role_id: i32,
+            granted_by: String,
+        }"#,
+        )
+        .unwrap();
+        let input = DeriveInput::from(ast);
+        let meta = ModelMeta::from_derive(&input);
+        let pkeys = meta.pkeys.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(pkeys, ["user_id", "role_id"]);
+        let rest = meta.database_columns_except_pkey().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(rest, ["granted_by"]);
+    }
+
+    #[test]
+    fn test_soft_delete() {
+        let ast = syn::parse_str::<ItemStruct>(
+            r#"#[ormlite(soft_delete = "deleted_at")]
+            struct User {
+            id: i32,
+            deleted_at: Option<String>,
+        }"#,
+        )
+        .unwrap();
+        let input = DeriveInput::from(ast);
+        let meta = ModelMeta::from_derive(&input);
+        assert_eq!(meta.soft_delete.as_deref(), Some("deleted_at"));
     }
 }
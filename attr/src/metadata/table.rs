@@ -2,7 +2,7 @@ use crate::metadata::column::ColumnMeta;
 use crate::DeriveInputExt;
 use crate::Ident;
 use convert_case::{Case, Casing};
-use structmeta::StructMeta;
+use structmeta::{Flag, StructMeta};
 use syn::{Attribute, DeriveInput, LitStr};
 
 /// Metadata used for IntoArguments, TableMeta, and (subset of) Model
@@ -14,9 +14,16 @@ pub struct TableMeta {
     pub columns: Vec<ColumnMeta>,
     pub databases: Vec<String>,
 
-    /// If you're using this, consider whether you should be using a ModelMetadata and its pkey,
-    /// which is not optional, instead.
-    pub pkey: Option<String>,
+    /// The primary key columns. Several columns make a composite primary key.
+    /// If you're using this, consider whether you should be using a ModelMetadata and its pkeys,
+    /// which are never empty, instead.
+    pub pkeys: Vec<String>,
+
+    /// The column set by `#[ormlite(soft_delete = "...")]`, which marks rows as deleted instead of deleting them.
+    pub soft_delete: Option<String>,
+
+    /// Whether the model implements `ModelHooks`, set by `#[ormlite(hooks)]`.
+    pub hooks: bool,
 }
 
 impl TableMeta {
@@ -28,25 +35,36 @@ impl TableMeta {
             ident.to_string().to_case(Case::Snake)
         };
         let mut columns = ColumnMeta::from_fields(ast.fields());
-        let mut pkey = columns
+        let mut pkeys = columns
             .iter()
-            .find(|&c| c.marked_primary_key)
-            .map(|c| c.clone())
-            .map(|c| c.name.clone());
-        if pkey.is_none() {
+            .filter(|&c| c.marked_primary_key)
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        if pkeys.is_empty() {
             let candidates = sql::util::pkey_column_names(&name);
             if let Some(c) = columns.iter_mut().find(|c| candidates.iter().any(|n| c.ident == n)) {
                 c.has_database_default = true;
-                pkey = Some(c.name.clone());
+                pkeys.push(c.name.clone());
             }
         }
         let databases = attrs.iter().flat_map(|d| &d.database).map(|d| d.value()).collect();
+        let soft_delete = attrs.iter().find_map(|a| a.soft_delete.as_ref()).map(|c| c.value());
+        if columns.iter().filter(|c| c.version).count() > 1 {
+            panic!("{ident} can only have one version column");
+        }
+        if let Some(column) = &soft_delete
+            && !columns.iter().any(|c| !c.skip && &c.name == column)
+        {
+            panic!("{ident} has no column `{column}` to soft delete with. Add it as an Option field.");
+        }
         Self {
             name,
             ident: Ident::from(ident),
             columns,
             databases,
-            pkey,
+            pkeys,
+            soft_delete,
+            hooks: attrs.iter().any(|a| a.hooks.value()),
         }
     }
 
@@ -66,18 +84,42 @@ impl TableMeta {
             .filter(|&c| !c.is_join() || c.is_join_one())
     }
 
+    /// The column marked `#[ormlite(updated_at)]`, if any.
+    pub fn updated_at(&self) -> Option<&ColumnMeta> {
+        self.database_columns().find(|c| c.updated_at)
+    }
+
+    /// The column marked `#[ormlite(version)]`, if any.
+    pub fn version(&self) -> Option<&ColumnMeta> {
+        self.database_columns().find(|c| c.version)
+    }
+
     pub fn many_to_one_joins(&self) -> impl Iterator<Item = &ColumnMeta> + '_ {
         self.columns.iter().filter(|&c| c.is_join_one())
     }
 
+    /// The column a join table uses to reference this table, e.g. `user_id` for a `user` table with an `id` primary key.
+    pub fn join_table_key(&self) -> String {
+        let [pkey] = self.pkeys.as_slice() else {
+            panic!("{} needs a single column primary key to be in a join table", self.ident);
+        };
+        if pkey.starts_with(&format!("{}_", self.name)) {
+            pkey.to_string()
+        } else {
+            format!("{}_{}", self.name, pkey)
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn mock(name: &str, columns: Vec<ColumnMeta>) -> Self {
         TableMeta {
             name: name.to_string(),
             ident: Ident::from(name.to_case(Case::Pascal)),
-            pkey: None,
+            pkeys: Vec::new(),
             columns,
             databases: vec![],
+            soft_delete: None,
+            hooks: false,
         }
     }
 }
@@ -115,6 +157,25 @@ pub struct TableAttr {
     ///
     pub extra_derives: Option<Vec<syn::Ident>>,
 
+    /// Soft delete rows by setting this column, a nullable timestamp, instead of deleting them. Queries skip
+    /// the deleted rows unless asked for them.
+    /// Example:
+    /// #[ormlite(soft_delete = "deleted_at")]
+    /// pub struct User {
+    ///   pub id: i32,
+    ///   pub deleted_at: Option<DateTime<Utc>>,
+    /// }
+    pub soft_delete: Option<LitStr>,
+
+    /// Call the model's `ModelHooks` implementation when it's inserted, updated or deleted.
+    /// Example:
+    /// #[ormlite(hooks)]
+    /// pub struct User {
+    ///   pub id: i32,
+    /// }
+    /// impl ModelHooks<Postgres> for User { ... }
+    pub hooks: Flag,
+
     /// Only used for derive(Insert)
     /// Example:
     /// #[ormlite(returns = "User")]
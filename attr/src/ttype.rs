@@ -13,9 +13,11 @@ pub enum Type {
 }
 
 impl Type {
+    /// The type inside a `Join`, including for an optional join, e.g. `Option<Join<T>>`.
     pub fn joined_type(&self) -> Option<&Type> {
         match &self {
             Type::Join(ty) => Some(ty.as_ref()),
+            Type::Option(ty) => ty.joined_type(),
             _ => None,
         }
     }
@@ -35,8 +37,13 @@ impl Type {
         }
     }
 
+    /// Whether the type is a `Join`, or an optional join, e.g. `Option<Join<T>>`.
     pub fn is_join(&self) -> bool {
-        matches!(self, Type::Join(_))
+        match self {
+            Type::Join(_) => true,
+            Type::Option(ty) => ty.is_join(),
+            _ => false,
+        }
     }
 
     pub fn is_option(&self) -> bool {
@@ -207,6 +214,15 @@ mod tests {
         assert!(ty.is_json());
     }
 
+    #[test]
+    fn test_optional_join() {
+        use syn::Path;
+        let ty = Type::from(&syn::parse_str::<Path>("Option<Join<Organization>>").unwrap());
+        assert!(ty.is_join());
+        assert!(ty.is_option());
+        assert_eq!(ty.joined_type().unwrap().inner_type_name(), "Organization");
+    }
+
     #[test]
     fn test_other_type_to_quote() {
         use syn::Path;
@@ -5,7 +5,7 @@ use std::path::Path;
 
 use anyhow::{anyhow, Context, Error, Result};
 use clap::Parser;
-use sql::{migrate::Statement, Dialect, Migration, Schema, ToSql};
+use sql::{migrate::Statement, Dialect, Migration, Schema};
 use sql_sqlx::FromPostgres;
 use time::macros::format_description;
 use time::OffsetDateTime as DateTime;
@@ -18,6 +18,7 @@ use ormlite::postgres::PgConnection;
 use ormlite::Row;
 use ormlite::{Acquire, Connection};
 use ormlite_core::config;
+use ormlite_core::schema::statement_to_sql;
 use ormlite_core::config::get_var_model_folders;
 
 const GET_MIGRATIONS_QUERY: &str = "SELECT
@@ -256,7 +257,7 @@ impl Migrate {
 
             if self.dry {
                 for statement in migration.statements {
-                    println!("{};", statement.to_sql(Dialect::Postgres));
+                    println!("{};", statement_to_sql(&statement, Dialect::Postgres));
                 }
                 return Ok(());
             }
@@ -274,7 +275,7 @@ impl Migrate {
             .map(|m| {
                 m.statements
                     .iter()
-                    .map(|s| s.to_sql(Dialect::Postgres) + ";")
+                    .map(|s| statement_to_sql(s, Dialect::Postgres) + ";")
                     .collect::<Vec<_>>()
                     .join("\n")
             })
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use sql::{Constraint, Schema, Table};
 use ormlite_attr::{schema_from_filepaths, Ident, InnerType, Type};
-use ormlite_core::schema::FromMeta;
+use ormlite_core::schema::{join_tables, primary_key_columns, FromMeta};
 use anyhow::Result as AnyResult;
 use crate::config::Config;
 
@@ -12,9 +12,10 @@ pub fn schema_from_ormlite_project(paths: &[&Path], c: &Config) -> AnyResult<Sch
     let primary_key_type: HashMap<String, InnerType> = fs_schema
         .tables
         .iter()
-        .map(|t| {
-            let pkey_ty = t.pkey.ty.inner_type().clone();
-            (t.ident.to_string(), pkey_ty)
+        // only models with a single column primary key can be joined
+        .filter_map(|t| match t.pkeys.as_slice() {
+            [pkey] => Some((t.ident.to_string(), pkey.ty.inner_type().clone())),
+            _ => None,
         })
         .collect();
     for t in &mut fs_schema.tables {
@@ -24,31 +25,47 @@ pub fn schema_from_ormlite_project(paths: &[&Path], c: &Config) -> AnyResult<Sch
             if let Some(f) = fs_schema.type_reprs.get(&inner.ident.to_string()) {
                 inner.ident = Ident::from(f);
             }
-            // replace join types with the primary key type.
-            if c.ty.is_join() {
+            // replace join types with the primary key type. Other joins don't have a column on this table.
+            if c.is_join_one() {
                 let model_name = c.ty.inner_type_name();
                 let pkey = primary_key_type
                     .get(&model_name)
                     .expect(&format!("Could not find model {} for join", model_name));
-                c.ty = Type::Inner(pkey.clone());
+                c.ty = if c.ty.is_option() {
+                    Type::Option(Box::new(Type::Inner(pkey.clone())))
+                } else {
+                    Type::Inner(pkey.clone())
+                };
             }
         }
     }
-    for table in fs_schema.tables {
-        let table = Table::from_meta(&table);
+    for table in &fs_schema.tables {
+        let table = Table::from_meta(table);
         schema.tables.push(table);
     }
-    let mut table_names: HashMap<String, (String, String)> =
-        schema.tables.iter().map(|t| (t.name.clone(), (t.name.clone(), t.primary_key().unwrap().name.clone()))).collect();
+    let join_tables = join_tables(&fs_schema.tables, &schema.tables);
+    // tables with a composite primary key can't be referenced by a single column
+    let mut table_names: HashMap<String, (String, String)> = schema
+        .tables
+        .iter()
+        .filter_map(|t| match primary_key_columns(t).as_slice() {
+            [pkey] => Some((t.name.clone(), (t.name.clone(), pkey.to_string()))),
+            _ => None,
+        })
+        .collect();
     for (alias, real) in &c.table.aliases {
         let Some(real) = table_names.get(real) else {
             continue;
         };
         table_names.insert(alias.clone(), real.clone());
     }
+    // join tables don't have a primary key, so add them after collecting the tables they can reference.
+    schema.tables.extend(join_tables);
     for table in &mut schema.tables {
+        // the columns of a composite primary key usually reference other tables
+        let composite = primary_key_columns(table).len() > 1;
         for column in &mut table.columns {
-            if column.primary_key {
+            if column.primary_key && !composite {
                 continue;
             }
             if column.name.ends_with("_id") || column.name.ends_with("_uuid") {
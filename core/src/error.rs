@@ -10,4 +10,13 @@ pub enum Error {
 
     #[error("{0}")]
     OrmliteError(String),
+
+    /// An update of a model with a `#[ormlite(version)]` column matched no rows, because the row was updated or
+    /// deleted since the model was read.
+    #[error("The row was updated or deleted since it was read")]
+    StaleData,
+
+    /// A field failed a `#[ormlite(validate(...))]` check.
+    #[error("{field} {message}")]
+    Validation { field: &'static str, message: String },
 }
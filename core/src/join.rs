@@ -1,4 +1,6 @@
 use crate::model::Model;
+use crate::query_builder::DatabaseMetadata;
+use futures::future::BoxFuture;
 use serde::de::Error;
 use serde::Deserialize;
 use serde::{Serialize, Serializer};
@@ -29,13 +31,12 @@ impl<T: JoinMeta> JoinMeta for Join<T> {
     }
 }
 
-// impl<T: JoinMeta> JoinMeta for Vec<T> {
-//     type IdType = T::IdType;
+/// A `Join<Vec<T>>` is keyed by the local model's primary key, so it doesn't store an id of its own.
+impl<T: JoinMeta> JoinMeta for Vec<T> {
+    type IdType = ();
 
-//     fn _id(&self) -> Self::IdType {
-//         unimplemented!()
-//     }
-// }
+    fn _id(&self) -> Self::IdType {}
+}
 
 pub trait Loadable<DB, T: JoinMeta> {
     #[allow(async_fn_in_trait)]
@@ -46,13 +47,45 @@ pub trait Loadable<DB, T: JoinMeta> {
         T: 's;
 }
 
+/// Load a join for every model in a collection, using one query for the whole collection rather than one per model.
+///
+/// ```ignore
+/// let mut users = User::select().fetch_all(&mut conn).await?;
+/// users.load_all(User::organization(), &mut conn).await?;
+/// ```
+pub trait LoadAll<DB: Database> {
+    fn load_all<'a, A>(&'a mut self, join: JoinDescription, db: A) -> BoxFuture<'a, crate::error::Result<()>>
+    where
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>;
+}
+
+impl<DB, M> LoadAll<DB> for [M]
+where
+    DB: Database,
+    M: Model<DB> + Send,
+{
+    fn load_all<'a, A>(&'a mut self, join: JoinDescription, db: A) -> BoxFuture<'a, crate::error::Result<()>>
+    where
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>,
+    {
+        Box::pin(async move {
+            if let JoinDescription::Nested(_) = join {
+                return Err(crate::Error::OrmliteError(
+                    "load_all doesn't support nested joins. Use SelectQueryBuilder::join instead.".to_string(),
+                ));
+            }
+            let mut conn = db.acquire().await?;
+            M::_load_join(self, join, &mut *conn).await
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Join<T: JoinMeta> {
     pub id: T::IdType,
     data: JoinData<T>,
 }
 
-/// Only represents a many-to-one relationship.
 #[derive(Debug)]
 pub enum JoinData<T: JoinMeta> {
     NotQueried,
@@ -133,8 +166,8 @@ impl<T: JoinMeta> Join<T> {
 impl<DB, T> Loadable<DB, T> for Join<T>
 where
     DB: Database,
-    T: JoinMeta + Model<DB> + Send,
-    T::IdType: for<'a> Encode<'a, DB> + for<'a> Decode<'a, DB> + Type<DB>,
+    T: JoinMeta + Model<DB, PrimaryKeyKind = crate::model::Single> + Send,
+    T::IdType: std::fmt::Debug + for<'a> Encode<'a, DB> + for<'a> Decode<'a, DB> + Type<DB>,
 {
     async fn load<'s, 'e, E: sqlx::Executor<'e, Database = DB> + 'e>(
         &'s mut self,
@@ -151,6 +184,16 @@ where
     }
 }
 
+impl<T> Default for Join<T>
+where
+    T: JoinMeta,
+    T::IdType: Default,
+{
+    fn default() -> Self {
+        Self::new_with_id(T::IdType::default())
+    }
+}
+
 impl<T: JoinMeta> Deref for Join<T> {
     type Target = T;
 
@@ -180,7 +223,7 @@ impl<T: JoinMeta> DerefMut for Join<T> {
 
 /// Not meant for end users.
 #[doc(hidden)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum JoinDescription {
     ManyToOne {
         /// the columns of the joined table
@@ -192,20 +235,139 @@ pub enum JoinDescription {
         /// the field on the local object. joined table is aliased to this to prevent conflicts.
         field: &'static str,
         foreign_key: &'static str,
+        /// the soft delete column of the joined table, whose deleted rows aren't joined
+        soft_delete_column: Option<&'static str>,
     },
+    /// Loaded with a second query, `WHERE foreign_key IN (...)`, after the main query runs.
+    OneToMany {
+        /// the name of the joined table
+        foreign_table: &'static str,
+        /// the primary key of the local table
+        local_column: &'static str,
+        /// the field on the local object.
+        field: &'static str,
+        /// the column on the joined table that references the local table
+        foreign_key: &'static str,
+    },
+    /// Loaded with a second query through the join table, after the main query runs.
+    ManyToMany {
+        /// the name of the joined table
+        foreign_table: &'static str,
+        /// the primary key of the local table
+        local_column: &'static str,
+        /// the field on the local object.
+        field: &'static str,
+        /// the primary key of the joined table
+        foreign_key: &'static str,
+        /// the name of the join table
+        join_table: &'static str,
+        /// the column on the join table that references the local table
+        join_table_local_key: &'static str,
+        /// the column on the join table that references the joined table
+        join_table_foreign_key: &'static str,
+    },
+    /// A path of many to one joins, each on the model joined by the previous one. Created with [`JoinDescription::then`].
+    Nested(Vec<JoinDescription>),
+}
+
+impl JoinDescription {
+    /// The field on the local object that the join populates.
+    pub fn field(&self) -> &'static str {
+        match self {
+            JoinDescription::ManyToOne { field, .. } => field,
+            JoinDescription::OneToMany { field, .. } => field,
+            JoinDescription::ManyToMany { field, .. } => field,
+            JoinDescription::Nested(path) => path[0].field(),
+        }
+    }
+
+    /// Join a relation of the joined model, e.g. `User::organization().then(Organization::owner())`
+    /// loads `user.organization.owner` in the same query. Every join of the chain must be many to one, or
+    /// building the query fails with an error.
+    pub fn then(self, join: JoinDescription) -> JoinDescription {
+        let mut path = match self {
+            JoinDescription::Nested(path) => path,
+            join => vec![join],
+        };
+        match join {
+            JoinDescription::Nested(rest) => path.extend(rest),
+            join => path.push(join),
+        }
+        JoinDescription::Nested(path)
+    }
+
+    /// Relate two models through the join table of a many to many join. Does nothing if they're already related.
+    ///
+    /// ```ignore
+    /// User::roles().attach(user.id, role.id, &mut db).await?;
+    /// ```
+    pub async fn attach<'e, DB, L, F, E>(&self, local_id: L, foreign_id: F, db: E) -> crate::error::Result<()>
+    where
+        DB: Database + DatabaseMetadata,
+        L: Send + for<'q> Encode<'q, DB> + Type<DB>,
+        F: Send + for<'q> Encode<'q, DB> + Type<DB>,
+        E: 'e + sqlx::Executor<'e, Database = DB>,
+        for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+    {
+        let (join_table, local_key, foreign_key) = self.join_table("attach")?;
+        let mut placeholder = DB::placeholder();
+        let query = format!(
+            "INSERT INTO \"{join_table}\" (\"{local_key}\", \"{foreign_key}\") VALUES ({}, {}) ON CONFLICT DO NOTHING",
+            placeholder.next().unwrap(),
+            placeholder.next().unwrap(),
+        );
+        sqlx::query(&query).bind(local_id).bind(foreign_id).execute(db).await?;
+        Ok(())
+    }
+
+    /// Remove the relation between two models from the join table of a many to many join.
+    ///
+    /// ```ignore
+    /// User::roles().detach(user.id, role.id, &mut db).await?;
+    /// ```
+    pub async fn detach<'e, DB, L, F, E>(&self, local_id: L, foreign_id: F, db: E) -> crate::error::Result<()>
+    where
+        DB: Database + DatabaseMetadata,
+        L: Send + for<'q> Encode<'q, DB> + Type<DB>,
+        F: Send + for<'q> Encode<'q, DB> + Type<DB>,
+        E: 'e + sqlx::Executor<'e, Database = DB>,
+        for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+    {
+        let (join_table, local_key, foreign_key) = self.join_table("detach")?;
+        let mut placeholder = DB::placeholder();
+        let query = format!(
+            "DELETE FROM \"{join_table}\" WHERE \"{local_key}\" = {} AND \"{foreign_key}\" = {}",
+            placeholder.next().unwrap(),
+            placeholder.next().unwrap(),
+        );
+        sqlx::query(&query).bind(local_id).bind(foreign_id).execute(db).await?;
+        Ok(())
+    }
+
+    fn join_table(&self, action: &str) -> crate::error::Result<(&'static str, &'static str, &'static str)> {
+        match self {
+            JoinDescription::ManyToMany {
+                join_table,
+                join_table_local_key,
+                join_table_foreign_key,
+                ..
+            } => Ok((join_table, join_table_local_key, join_table_foreign_key)),
+            _ => Err(crate::Error::OrmliteError(format!(
+                "Can only {action} with a many to many join, but `{}` is not one",
+                self.field()
+            ))),
+        }
+    }
 }
 
 pub fn column_alias(field: &str, column: &str) -> String {
     format!("__{}__{}", field, column)
 }
 
-pub fn select_columns(
-    columns: &'static [&'static str],
-    field: &'static str,
-) -> impl Iterator<Item = SelectColumn> + 'static {
+pub fn select_columns<'a>(columns: &'static [&'static str], table: &'a str) -> impl Iterator<Item = SelectColumn> + 'a {
     columns
         .iter()
-        .map(|&c| SelectColumn::table_column(field, c).alias(column_alias(field, c)))
+        .map(move |&c| SelectColumn::table_column(table, c).alias(column_alias(table, c)))
 }
 
 pub fn criteria(local_table: &str, local_column: &str, remote_table: &str, remote_column: &str) -> Criteria {
@@ -10,3 +10,4 @@ pub mod join;
 pub mod model;
 pub mod query_builder;
 pub mod schema;
+pub mod validate;
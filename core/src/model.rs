@@ -7,6 +7,8 @@
 ///  - `ormlite::TableMeta`, which you typically don't use directly, but provides table metadata
 ///    (e.g. table name)
 ///
+use crate::join::JoinDescription;
+use crate::query_builder::{DatabaseMetadata, DeleteQueryBuilder, QueryBuilderArgs, UpdateQueryBuilder};
 use crate::Result;
 use crate::SelectQueryBuilder;
 use futures::future::BoxFuture;
@@ -44,19 +46,30 @@ where
 {
     type Model;
 
-    fn insert<'e: 'a, E>(self, db: E) -> BoxFuture<'a, Result<Self::Model>>
+    fn insert<A>(self, db: A) -> BoxFuture<'a, Result<Self::Model>>
     where
-        E: 'e + sqlx::Executor<'e, Database = DB>;
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>;
 
-    fn update<'e: 'a, E>(self, db: E) -> BoxFuture<'a, Result<Self::Model>>
+    fn update<A>(self, db: A) -> BoxFuture<'a, Result<Self::Model>>
     where
-        E: 'e + sqlx::Executor<'e, Database = DB>;
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>;
+
+    /// Like `update`, but also updates the modified joined models the builder holds, e.g. after
+    /// `user.organization.name = ...`. Everything is updated in one transaction. Defaults to `update`, for models
+    /// without joins.
+    fn update_cascade<A>(self, db: A) -> BoxFuture<'a, Result<Self::Model>>
+    where
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>,
+    {
+        self.update(db)
+    }
 
     /// All fields that will be modified in the query.
     fn modified_fields(&self) -> Vec<&'static str>;
 
-    /// Build the model, don't insert or update it.
-    fn build(self) -> Self::Model;
+    /// Build the model, don't insert or update it. Fails if a field is invalid, as marked by
+    /// `#[ormlite(validate(...))]`.
+    fn build(self) -> Result<Self::Model>;
 }
 
 /// The core trait. a struct that implements `Model` can also implement `HasModelBuilder`, (and is required to implement `Insertable`)
@@ -69,32 +82,71 @@ where
     where
         Self: 'a;
 
+    /// `Single` or `Composite`, depending on the number of primary key columns. It decides whether `fetch_one`
+    /// takes a value or a tuple.
+    type PrimaryKeyKind;
+
     /// Insert the model into the database.
     fn insert<'a, A>(self, conn: A) -> crate::insert::Insertion<'a, A, Self, DB>
     where
         A: 'a + Send + sqlx::Acquire<'a, Database = DB>,
         Self: Send;
 
-    fn insert_many<'e, E>(values: Vec<Self>, db: E) -> BoxFuture<'e, Result<Vec<Self>>>
+    fn insert_many<'a, A>(values: Vec<Self>, db: A) -> BoxFuture<'a, Result<Vec<Self>>>
     where
-        E: 'e + sqlx::Executor<'e, Database = DB>;
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>;
 
     /// `Model` objects can't track what fields are updated, so this method will update all fields.
     /// If you want to update only some fields, use `update_partial` instead.
-    fn update_all_fields<'e, E>(self, db: E) -> BoxFuture<'e, Result<Self>>
+    fn update_all_fields<'a, A>(self, db: A) -> BoxFuture<'a, Result<Self>>
+    where
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>;
+
+    /// Like `update_all_fields`, but also updates the modified joined models, e.g. after
+    /// `user.organization.name = ...`, which `update_all_fields` ignores. Joined models are updated
+    /// the same way, and everything is updated in one transaction. Defaults to `update_all_fields`, for models
+    /// without joins.
+    fn update_all_fields_cascade<'a, A>(self, db: A) -> BoxFuture<'a, Result<Self>>
+    where
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>,
+    {
+        self.update_all_fields(db)
+    }
+
+    /// Delete the model. With `#[ormlite(soft_delete = "...")]`, the row is marked deleted instead.
+    fn delete<'a, A>(self, db: A) -> BoxFuture<'a, Result<()>>
     where
-        E: 'e + Send + sqlx::Executor<'e, Database = DB>;
+        A: 'a + Send + sqlx::Acquire<'a, Database = DB>;
 
-    fn delete<'e, E>(self, db: E) -> BoxFuture<'e, Result<()>>
+    /// Undo a soft delete, and return the restored model. Errors for models without
+    /// `#[ormlite(soft_delete = "...")]`.
+    fn restore<'e, E>(self, db: E) -> BoxFuture<'e, Result<Self>>
     where
-        E: 'e + sqlx::Executor<'e, Database = DB>;
+        E: 'e + sqlx::Executor<'e, Database = DB>,
+    {
+        let _ = db;
+        Box::pin(async move {
+            Err(crate::Error::OrmliteError(format!(
+                "{} has no soft delete column, so it can't be restored",
+                Self::table_name()
+            )))
+        })
+    }
 
-    /// Get by primary key.
+    /// Delete by primary key, and return the number of rows deleted. For a composite primary key, pass a tuple
+    /// of the values in column order.
+    fn delete_by_id<'e, 'a, Arg, E>(id: Arg, db: E) -> BoxFuture<'e, Result<u64>>
+    where
+        'a: 'e,
+        E: 'e + sqlx::Executor<'e, Database = DB>,
+        Arg: PrimaryKey<'a, DB, Self::PrimaryKeyKind>;
+
+    /// Get by primary key. For a composite primary key, pass a tuple of the values in column order.
     fn fetch_one<'e, 'a, Arg, E>(id: Arg, db: E) -> BoxFuture<'e, Result<Self>>
     where
         'a: 'e,
         E: 'e + sqlx::Executor<'e, Database = DB>,
-        Arg: 'a + Send + sqlx::Encode<'a, DB> + sqlx::Type<DB>;
+        Arg: PrimaryKey<'a, DB, Self::PrimaryKeyKind>;
 
     /// If query building isn't meeting your needs, use this method to query the table using raw SQL.
     fn query(query: &str) -> sqlx::query::QueryAs<'_, DB, Self, DB::Arguments<'_>>;
@@ -102,17 +154,176 @@ where
     /// Create a `SelectQueryBuilder` to build a query.
     fn select<'args>() -> SelectQueryBuilder<'args, DB, Self>;
 
+    /// Create an `UpdateQueryBuilder` to update every row that matches a condition.
+    fn update_where<'args>() -> UpdateQueryBuilder<'args, DB, Self>
+    where
+        DB: DatabaseMetadata,
+    {
+        UpdateQueryBuilder::default()
+    }
+
+    /// Create a `DeleteQueryBuilder` to delete every row that matches a condition.
+    fn delete_where<'args>() -> DeleteQueryBuilder<'args, DB, Self>
+    where
+        DB: DatabaseMetadata,
+    {
+        DeleteQueryBuilder::default()
+    }
+
     /// Create a builder-pattern object to update one or more columns.
     /// You can also use `update_all_fields` to update all columns.
     fn update_partial(&self) -> Self::ModelBuilder<'_>;
 
     fn builder() -> Self::ModelBuilder<'static>;
+
+    /// Load a join into each of the models with a single query.
+    /// Not meant for end users; `SelectQueryBuilder::join` calls this after the main query runs for joins that
+    /// can't be part of the main query (e.g. one to many), and `LoadAll::load_all` calls it for any join.
+    #[doc(hidden)]
+    fn _load_join<'a>(
+        models: &'a mut [Self],
+        join: JoinDescription,
+        conn: &'a mut DB::Connection,
+    ) -> BoxFuture<'a, Result<()>>
+    where
+        Self: Send,
+    {
+        let _ = (models, conn);
+        Box::pin(async move {
+            Err(crate::Error::OrmliteError(format!(
+                "{} does not know how to load the join `{}`",
+                Self::table_name(),
+                join.field()
+            )))
+        })
+    }
 }
 
+/// Lifecycle hooks for a model marked `#[ormlite(hooks)]`, e.g. to normalize fields or write audit rows. The
+/// generated `insert`, `insert_many`, `update_all_fields`, `delete` and `ModelBuilder` methods run the hooks, the
+/// model's validation and the query in one transaction, and pass the hooks its connection. An error from any of them
+/// rolls back the transaction, including what the hooks wrote. Every hook does nothing by default.
+///
+/// `before_*` hooks run before validation, so validation checks the fields they set.
+///
+/// `ModelBuilder::insert` and `ModelBuilder::update` only have the fields being set, so they call
+/// `before_insert_partial` and `before_update_partial` with the builder instead.
+pub trait ModelHooks<DB>: Model<DB> + Send
+where
+    DB: sqlx::Database,
+{
+    fn before_insert<'a>(&'a mut self, conn: &'a mut DB::Connection) -> BoxFuture<'a, Result<()>> {
+        let _ = conn;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called with the inserted model, as returned by the database.
+    fn after_insert<'a>(&'a mut self, conn: &'a mut DB::Connection) -> BoxFuture<'a, Result<()>> {
+        let _ = conn;
+        Box::pin(async { Ok(()) })
+    }
+
+    fn before_update<'a>(&'a mut self, conn: &'a mut DB::Connection) -> BoxFuture<'a, Result<()>> {
+        let _ = conn;
+        Box::pin(async { Ok(()) })
+    }
+
+    fn before_delete<'a>(&'a mut self, conn: &'a mut DB::Connection) -> BoxFuture<'a, Result<()>> {
+        let _ = conn;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Like `before_insert`, for `ModelBuilder::insert`. Fields the hook sets are inserted too.
+    fn before_insert_partial<'a, 'b>(
+        builder: &'a mut Self::ModelBuilder<'b>,
+        conn: &'a mut DB::Connection,
+    ) -> BoxFuture<'a, Result<()>> {
+        let _ = (builder, conn);
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Like `before_update`, for `ModelBuilder::update`. Fields the hook sets are updated too.
+    fn before_update_partial<'a, 'b>(
+        builder: &'a mut Self::ModelBuilder<'b>,
+        conn: &'a mut DB::Connection,
+    ) -> BoxFuture<'a, Result<()>> {
+        let _ = (builder, conn);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Marks a model with a single column primary key. See `PrimaryKey`.
+pub struct Single;
+
+/// Marks a model with a composite primary key. See `PrimaryKey`.
+pub struct Composite;
+
+/// Values of a primary key, as taken by `Model::fetch_one`: a value for a single column primary key, or a tuple of
+/// values, in column order, for a composite primary key. `Kind` is `Single` or `Composite`, which keeps a single
+/// value from being mistaken for a tuple.
+pub trait PrimaryKey<'a, DB: sqlx::Database, Kind>: 'a + Send {
+    /// Add the values to `args`, in column order.
+    fn add_to(self, args: &mut QueryBuilderArgs<'a, DB>);
+}
+
+impl<'a, DB, T> PrimaryKey<'a, DB, Single> for T
+where
+    DB: sqlx::Database,
+    T: 'a + Send + std::fmt::Debug + sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    fn add_to(self, args: &mut QueryBuilderArgs<'a, DB>) {
+        args.add(self);
+    }
+}
+
+macro_rules! impl_composite_primary_key {
+    ($($t:ident),+) => {
+        impl<'a, DB, $($t),+> PrimaryKey<'a, DB, Composite> for ($($t,)+)
+        where
+            DB: sqlx::Database,
+            $($t: 'a + Send + std::fmt::Debug + sqlx::Encode<'a, DB> + sqlx::Type<DB>,)+
+        {
+            #[allow(non_snake_case)]
+            fn add_to(self, args: &mut QueryBuilderArgs<'a, DB>) {
+                let ($($t,)+) = self;
+                $(args.add($t);)+
+            }
+        }
+    };
+}
+
+impl_composite_primary_key!(A, B);
+impl_composite_primary_key!(A, B, C);
+impl_composite_primary_key!(A, B, C, D);
+impl_composite_primary_key!(A, B, C, D, E);
+
 pub trait TableMeta {
     fn table_name() -> &'static str;
     fn table_columns() -> &'static [&'static str];
+    /// The primary key column. `None` if the table has no primary key, or a composite one.
     fn primary_key() -> Option<&'static str>;
+    /// All primary key columns, in order. Several columns make a composite primary key. Defaults to the
+    /// `primary_key` column.
+    fn primary_key_columns() -> &'static [&'static str] {
+        let columns = Self::table_columns();
+        match Self::primary_key().and_then(|pkey| columns.iter().position(|&c| c == pkey)) {
+            Some(i) => &columns[i..=i],
+            None => &[],
+        }
+    }
+    /// The column marked `#[ormlite(updated_at)]`, which updates set to the current time.
+    fn updated_at_column() -> Option<&'static str> {
+        None
+    }
+    /// The column marked `#[ormlite(version)]`, which updates increment.
+    fn version_column() -> Option<&'static str> {
+        None
+    }
+    /// The column set by `#[ormlite(soft_delete = "...")]`. Deletes set it to the current time instead of deleting
+    /// the row, and selects skip the rows where it's set.
+    fn soft_delete_column() -> Option<&'static str> {
+        None
+    }
     fn primary_key_placeholder_idx() -> Option<usize> {
         let col = Self::primary_key()?;
         Self::table_columns().iter().position(|&c| c == col).map(|i| i + 1)
@@ -1,27 +1,108 @@
 use core::default::Default;
-use sqlx::{Arguments, Database, IntoArguments};
+use sqlx::{Arguments, Database, Encode, IntoArguments, Type, TypeInfo};
+use std::fmt::Debug;
 
-pub struct QueryBuilderArgs<'q, DB: Database>(pub Box<DB::Arguments<'q>>, usize);
+/// A bound value. It's only encoded once the query runs, and can be printed to debug the query.
+trait Argument<'q, DB: Database>: Debug + Send + 'q {
+    fn add_to(self: Box<Self>, args: &mut DB::Arguments<'q>);
+}
+
+impl<'q, DB: Database, T: 'q + Send + Debug + Encode<'q, DB> + Type<DB>> Argument<'q, DB> for T {
+    fn add_to(self: Box<Self>, args: &mut DB::Arguments<'q>) {
+        args.add(*self).unwrap();
+    }
+}
+
+/// The values bound to one `?` placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Width {
+    One,
+    /// A list, which expands to a placeholder per value. `sql_type` is the type of its values, to select no rows of
+    /// that type if it's empty.
+    List {
+        len: usize,
+        sql_type: String,
+    },
+}
+
+/// Arguments of a query, in placeholder order. Values are only encoded once the query runs, so
+/// arguments bound for different parts of a query can be put in order with [`extend`](Self::extend).
+pub struct QueryBuilderArgs<'q, DB: Database> {
+    values: Vec<Box<dyn Argument<'q, DB>>>,
+    /// The values bound to each `?` placeholder. A list binds several values to one placeholder.
+    widths: Vec<Width>,
+}
 
 impl<'q, DB: Database> QueryBuilderArgs<'q, DB> {
-    pub fn add<T: 'q + Send + sqlx::Encode<'q, DB> + sqlx::Type<DB>>(&mut self, arg: T) {
-        self.0.add(arg).unwrap();
-        self.1 += 1;
+    pub fn add<T: 'q + Send + Debug + Encode<'q, DB> + Type<DB>>(&mut self, arg: T) {
+        self.values.push(Box::new(arg));
+        self.widths.push(Width::One);
     }
 
+    /// Bind all of `args` to a single placeholder, which expands to one placeholder per value when the
+    /// query is built, e.g. `IN (?)` becomes `IN (?, ?, ?)`. No values expand to a subquery without rows, so `IN (?)`
+    /// matches no rows and `NOT IN (?)` matches all of them.
+    pub fn add_list<T, I>(&mut self, args: I)
+    where
+        T: 'q + Send + Debug + Encode<'q, DB> + Type<DB>,
+        I: IntoIterator<Item = T>,
+    {
+        let before = self.values.len();
+        for arg in args {
+            self.values.push(Box::new(arg));
+        }
+        self.widths.push(Width::List {
+            len: self.values.len() - before,
+            sql_type: T::type_info().name().to_string(),
+        });
+    }
+
+    /// Append the arguments of `other` after these ones.
+    pub fn extend(&mut self, other: Self) {
+        self.values.extend(other.values);
+        self.widths.extend(other.widths);
+    }
+
+    /// The number of values, which is larger than the number of placeholders if lists are bound.
     pub fn len(&self) -> usize {
-        self.1
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub(crate) fn placeholder_widths(&self) -> &[Width] {
+        &self.widths
+    }
+
+    /// The values, to print them.
+    pub(crate) fn values(&self) -> impl Iterator<Item = &dyn Debug> {
+        self.values.iter().map(|v| v as &dyn Debug)
+    }
+}
+
+impl<'q, DB: Database> Debug for QueryBuilderArgs<'q, DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.values()).finish()
     }
 }
 
 impl<'q, DB: Database> IntoArguments<'q, DB> for QueryBuilderArgs<'q, DB> {
     fn into_arguments(self) -> DB::Arguments<'q> {
-        *self.0
+        let mut args = DB::Arguments::default();
+        for value in self.values {
+            value.add_to(&mut args);
+        }
+        args
     }
 }
 
 impl<'q, DB: Database> Default for QueryBuilderArgs<'q, DB> {
     fn default() -> Self {
-        Self(Box::default(), 0)
+        Self {
+            values: Vec::new(),
+            widths: Vec::new(),
+        }
     }
 }
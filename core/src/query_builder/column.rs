@@ -0,0 +1,231 @@
+use crate::query_builder::args::QueryBuilderArgs;
+use sql::query::Where;
+use sql::{Dialect, Expr, Operation, ToSql};
+use std::marker::PhantomData;
+
+/// A column of model `M`, whose values are `T`. The `Model` derive generates one for each column, e.g.
+/// `User::columns().age`, so filters are checked by the compiler instead of failing at runtime when a field is renamed.
+///
+/// ```ignore
+/// let users = User::select()
+///     .where_(User::columns().age.gte(18).and(User::columns().name.like("A%")))
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+pub struct TypedColumn<M, T> {
+    pub table: &'static str,
+    pub name: &'static str,
+    _marker: PhantomData<fn() -> (M, T)>,
+}
+
+impl<M, T> Clone for TypedColumn<M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M, T> Copy for TypedColumn<M, T> {}
+
+impl<M, T> TypedColumn<M, T> {
+    pub const fn new(table: &'static str, name: &'static str) -> Self {
+        Self {
+            table,
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The column, qualified by its table, e.g. `"user"."age"`.
+    pub fn expr(&self) -> Expr {
+        Expr::table_column(self.table, self.name)
+    }
+
+    fn raw<'args, DB: sqlx::Database>(&self, op: &str) -> Condition<'args, DB> {
+        let column = self.expr().to_sql(Dialect::Postgres);
+        Condition::new(Where::Expr(Expr::Raw(format!("{column} {op}"))))
+    }
+
+    fn compare<'args, DB>(&self, op: Operation, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        let expr = Expr::BinOp(op, Box::new(self.expr()), Box::new(Expr::Raw("?".to_string())));
+        Condition::new(Where::Expr(expr)).bind(value.into())
+    }
+
+    pub fn eq<'args, DB>(self, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.compare(Operation::Eq, value)
+    }
+
+    pub fn ne<'args, DB>(self, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.raw("<> ?").bind(value.into())
+    }
+
+    pub fn gt<'args, DB>(self, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.compare(Operation::Gt, value)
+    }
+
+    pub fn gte<'args, DB>(self, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.compare(Operation::Gte, value)
+    }
+
+    pub fn lt<'args, DB>(self, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.compare(Operation::Lt, value)
+    }
+
+    pub fn lte<'args, DB>(self, value: impl Into<T>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.compare(Operation::Lte, value)
+    }
+
+    pub fn is_null<'args, DB: sqlx::Database>(self) -> Condition<'args, DB> {
+        self.raw("IS NULL")
+    }
+
+    pub fn is_not_null<'args, DB: sqlx::Database>(self) -> Condition<'args, DB> {
+        self.raw("IS NOT NULL")
+    }
+
+    /// `<column> IN (?, ?, ...)`, with a placeholder for each value, like
+    /// [`SelectQueryBuilder::where_in`](crate::query_builder::SelectQueryBuilder::where_in). No values match no rows.
+    pub fn in_<'args, DB, V>(self, values: impl IntoIterator<Item = V>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        V: Into<T>,
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        let mut condition = self.raw("IN (?)");
+        condition.args.add_list(values.into_iter().map(Into::into));
+        condition
+    }
+}
+
+impl<M> TypedColumn<M, String> {
+    /// `<column> LIKE ?`. The pattern uses SQL wildcards, e.g. `"A%"`.
+    pub fn like<'args, DB>(self, pattern: impl Into<String>) -> Condition<'args, DB>
+    where
+        DB: sqlx::Database,
+        String: sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.raw("LIKE ?").bind(pattern.into())
+    }
+}
+
+/// A WHERE condition along with the values bound to its placeholders. Built from a `TypedColumn`, and combined
+/// with `and` and `or`.
+pub struct Condition<'args, DB: sqlx::Database> {
+    pub(crate) where_: Where,
+    pub(crate) args: QueryBuilderArgs<'args, DB>,
+}
+
+impl<'args, DB: sqlx::Database> Condition<'args, DB> {
+    fn new(where_: Where) -> Self {
+        Self {
+            where_,
+            args: QueryBuilderArgs::default(),
+        }
+    }
+
+    fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Encode<'args, DB> + sqlx::Type<DB>,
+    {
+        self.args.add(value);
+        self
+    }
+
+    pub fn and(mut self, other: Self) -> Self {
+        self.where_ = match self.where_ {
+            Where::And(mut v) => {
+                v.push(other.where_);
+                Where::And(v)
+            }
+            w => Where::And(vec![w, other.where_]),
+        };
+        self.args.extend(other.args);
+        self
+    }
+
+    pub fn or(mut self, other: Self) -> Self {
+        self.where_ = match self.where_ {
+            Where::Or(mut v) => {
+                v.push(other.where_);
+                Where::Or(v)
+            }
+            w => Where::Or(vec![w, other.where_]),
+        };
+        self.args.extend(other.args);
+        self
+    }
+
+    pub fn into_parts(self) -> (Where, QueryBuilderArgs<'args, DB>) {
+        (self.where_, self.args)
+    }
+}
+
+/// Anything `SelectQueryBuilder::where_` accepts: a `&'static str` clause, whose arguments are passed with `bind`,
+/// or a `Condition`, which carries its own.
+pub trait IntoCondition<'args, DB: sqlx::Database> {
+    fn into_condition(self) -> Condition<'args, DB>;
+}
+
+impl<'args, DB: sqlx::Database> IntoCondition<'args, DB> for &'static str {
+    fn into_condition(self) -> Condition<'args, DB> {
+        Condition::new(Where::raw(self))
+    }
+}
+
+impl<'args, DB: sqlx::Database> IntoCondition<'args, DB> for Condition<'args, DB> {
+    fn into_condition(self) -> Condition<'args, DB> {
+        self
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    struct User;
+
+    #[test]
+    fn test_condition_sql() {
+        let age = TypedColumn::<User, i32>::new("user", "age");
+        let name = TypedColumn::<User, String>::new("user", "name");
+        let condition: Condition<sqlx::Sqlite> = age.gte(18).and(name.like("A%").or(name.is_null()));
+        assert_eq!(condition.args.len(), 2);
+        assert_eq!(
+            condition.where_.to_sql(Dialect::Sqlite),
+            r#""user"."age" >= ? AND ("user"."name" LIKE ? OR "user"."name" IS NULL)"#
+        );
+        // The list binds to a single placeholder, which expands when the query is built.
+        let condition: Condition<sqlx::Sqlite> = age.in_([1, 2, 3]);
+        assert_eq!(condition.where_.to_sql(Dialect::Sqlite), r#""user"."age" IN (?)"#);
+        assert_eq!(condition.args.len(), 3);
+        let condition: Condition<sqlx::Sqlite> = age.in_(Vec::<i32>::new());
+        assert_eq!(condition.args.len(), 0);
+    }
+}
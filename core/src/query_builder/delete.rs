@@ -0,0 +1,164 @@
+use crate::error::Result;
+use crate::model::{Model, TableMeta};
+use crate::query_builder::args::QueryBuilderArgs;
+use crate::query_builder::{DatabaseMetadata, IntoCondition, Placeholder, RowsAffected, util};
+use futures::future::BoxFuture;
+use sql::ToSql;
+use sql::query::Where;
+use sqlx::{Acquire, Executor, IntoArguments};
+use std::marker::PhantomData;
+
+/// Delete every row of a model's table that matches a condition, or mark it deleted if the model is soft deleted, e.g.
+///
+/// ```ignore
+/// let deleted = User::delete_where()
+///     .where_bind("organization_id = ?", org_id)
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+pub struct DeleteQueryBuilder<'args, DB, M>
+where
+    DB: sqlx::Database,
+{
+    where_: Where,
+    arguments: QueryBuilderArgs<'args, DB>,
+    placeholder: Placeholder,
+    model: PhantomData<M>,
+}
+
+impl<'args, DB, M> DeleteQueryBuilder<'args, DB, M>
+where
+    M: Sized + Send + Sync + Unpin + for<'r> sqlx::FromRow<'r, DB::Row> + 'static + Model<DB>,
+    DB: sqlx::Database + DatabaseMetadata,
+    DB::Arguments<'args>: IntoArguments<'args, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    /// Add a WHERE clause to the query. Like [`SelectQueryBuilder::where_`](super::SelectQueryBuilder::where_),
+    /// use `?` as the placeholder and add parameters with [`bind`](Self::bind), or pass a typed condition.
+    /// Without a WHERE clause, every row is deleted.
+    pub fn where_(mut self, clause: impl IntoCondition<'args, DB>) -> Self {
+        let (where_, args) = clause.into_condition().into_parts();
+        self.where_ = match self.where_ {
+            Where::And(mut v) => {
+                v.push(where_);
+                Where::And(v)
+            }
+            w => Where::And(vec![w, where_]),
+        };
+        self.arguments.extend(args);
+        self
+    }
+
+    /// Convenience method to add a `WHERE` and bind a value in one call.
+    pub fn where_bind<T>(self, clause: &'static str, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.where_(clause).bind(value)
+    }
+
+    /// Bind an argument to the query.
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.arguments.add(value);
+        self
+    }
+
+    /// Run the delete, and return the number of rows deleted.
+    pub fn execute<'a, A>(self, db: A) -> BoxFuture<'a, Result<u64>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        DB::QueryResult: RowsAffected,
+    {
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let result = util::query_with_recast_lifetime::<DB>(&text, args)
+                .execute(&mut *conn)
+                .await?;
+            Ok(result.rows_affected())
+        })
+    }
+
+    /// Run the delete, and return the deleted models.
+    pub fn fetch_all<'a, A>(self, db: A) -> BoxFuture<'a, Result<Vec<M>>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+    {
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let text = format!("{text} RETURNING *");
+            let mut conn = db.acquire().await?;
+            let models = util::query_as_with_recast_lifetime::<DB, M>(&text, args)
+                .fetch_all(&mut *conn)
+                .await?;
+            Ok(models)
+        })
+    }
+
+    pub fn into_query_and_args(mut self) -> Result<(String, QueryBuilderArgs<'args, DB>)> {
+        util::build_query_text(&self.text(), self.arguments, &mut self.placeholder)
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M: TableMeta> DeleteQueryBuilder<'args, DB, M> {
+    /// The query as it's sent to the database, with the database's placeholders, e.g. to log it or to snapshot it in
+    /// tests. Unlike [`into_query_and_args`](Self::into_query_and_args), it keeps the builder.
+    pub fn to_sql(&self) -> Result<String> {
+        util::replace_and_check_placeholders(&self.text(), &[&self.arguments], &mut DB::placeholder())
+    }
+
+    /// The query, keeping its `?` placeholders. Soft deleted models are marked deleted instead.
+    fn text(&self) -> String {
+        let table = M::table_name();
+        let mut conditions = Vec::new();
+        if !self.where_.is_empty() {
+            conditions.push(self.where_.to_sql(DB::dialect()));
+        }
+        let mut q = match M::soft_delete_column() {
+            Some(column) => {
+                // Parenthesized, so the soft delete condition applies to all of it.
+                if let Some(condition) = conditions.first_mut() {
+                    *condition = format!("({condition})");
+                }
+                conditions.push(format!("\"{column}\" IS NULL"));
+                format!("UPDATE \"{table}\" SET \"{column}\" = CURRENT_TIMESTAMP")
+            }
+            None => format!("DELETE FROM \"{table}\""),
+        };
+        if !conditions.is_empty() {
+            q.push_str(" WHERE ");
+            q.push_str(&conditions.join(" AND "));
+        }
+        q
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M> Default for DeleteQueryBuilder<'args, DB, M> {
+    fn default() -> Self {
+        Self {
+            where_: Where::And(Vec::new()),
+            arguments: QueryBuilderArgs::default(),
+            placeholder: DB::placeholder(),
+            model: PhantomData,
+        }
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M: TableMeta> std::fmt::Debug for DeleteQueryBuilder<'args, DB, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::debug_query(f, "DeleteQueryBuilder", self.to_sql(), &[&self.arguments])
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M: TableMeta> std::fmt::Display
+    for DeleteQueryBuilder<'args, DB, M>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::fmt_query(f, self.to_sql(), &[&self.arguments])
+    }
+}
@@ -0,0 +1,67 @@
+use crate::error::{Error, Result};
+use sql::Dialect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LockStrength {
+    Update,
+    Share,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LockWait {
+    Wait,
+    SkipLocked,
+    NoWait,
+}
+
+/// A row locking clause, e.g. `FOR UPDATE SKIP LOCKED`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RowLock {
+    pub strength: Option<LockStrength>,
+    pub wait: LockWait,
+}
+
+impl RowLock {
+    pub fn to_sql(self, dialect: Dialect) -> Result<String> {
+        let Some(strength) = self.strength else {
+            return Err(Error::OrmliteError(
+                "skip_locked and nowait need a lock, from for_update or for_share".to_string(),
+            ));
+        };
+        if dialect == Dialect::Sqlite {
+            return Err(Error::OrmliteError(
+                "SQLite doesn't support row locks (for_update, for_share, skip_locked, nowait)".to_string(),
+            ));
+        }
+        let mut sql = match strength {
+            LockStrength::Update => " FOR UPDATE".to_string(),
+            LockStrength::Share => " FOR SHARE".to_string(),
+        };
+        match self.wait {
+            LockWait::Wait => {}
+            LockWait::SkipLocked => sql.push_str(" SKIP LOCKED"),
+            LockWait::NoWait => sql.push_str(" NOWAIT"),
+        }
+        Ok(sql)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_lock_sql() {
+        let lock = RowLock {
+            strength: Some(LockStrength::Update),
+            wait: LockWait::SkipLocked,
+        };
+        assert_eq!(lock.to_sql(Dialect::Postgres).unwrap(), " FOR UPDATE SKIP LOCKED");
+        assert!(lock.to_sql(Dialect::Sqlite).is_err());
+        let lock = RowLock {
+            strength: None,
+            wait: LockWait::NoWait,
+        };
+        assert!(lock.to_sql(Dialect::Postgres).is_err());
+    }
+}
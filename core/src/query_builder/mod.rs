@@ -1,7 +1,18 @@
 pub mod args;
+mod column;
+mod delete;
+mod lock;
+mod page;
 pub mod placeholder;
+mod projection;
 mod select;
+mod update;
 mod util;
 pub use args::QueryBuilderArgs;
+pub use column::{Condition, IntoCondition, TypedColumn};
+pub use delete::DeleteQueryBuilder;
+pub use page::{Cursor, Page};
 pub use placeholder::Placeholder;
-pub use select::SelectQueryBuilder;
+pub use projection::ProjectionQueryBuilder;
+pub use select::{DatabaseMetadata, RowsAffected, SelectQueryBuilder};
+pub use update::UpdateQueryBuilder;
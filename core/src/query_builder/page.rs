@@ -0,0 +1,204 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A page of models from [`SelectQueryBuilder::paginate_after`](super::SelectQueryBuilder::paginate_after).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<M> {
+    pub items: Vec<M>,
+    /// Pass this to `paginate_after` to fetch the next page. `None` on the last page.
+    pub next_cursor: Option<Cursor>,
+}
+
+/// An opaque position in a query's ORDER BY, after the last model of a page. It serializes as a string, so it
+/// can be handed to API clients and parsed back with `str::parse`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Cursor(Vec<CursorValue>);
+
+/// The value of one ORDER BY key. SQLite compares values by their storage class, so its values keep theirs, and are
+/// bound back as integers, reals or text. Postgres values are kept as text, which Postgres prints exactly, with the
+/// name of their type to cast them back to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum CursorValue {
+    Integer(i64),
+    /// The bits of an `f64`, so it's kept exactly.
+    Real(u64),
+    Text {
+        type_name: String,
+        value: Option<String>,
+    },
+}
+
+impl Cursor {
+    pub(crate) fn new(values: Vec<CursorValue>) -> Self {
+        Self(values)
+    }
+
+    pub(crate) fn values(&self) -> &[CursorValue] {
+        &self.0
+    }
+}
+
+fn push_field(buf: &mut String, field: &str) {
+    buf.push_str(&field.len().to_string());
+    buf.push(':');
+    buf.push_str(field);
+}
+
+fn take_field<'s>(s: &mut &'s str) -> Option<&'s str> {
+    let (len, rest) = s.split_once(':')?;
+    let len = len.parse::<usize>().ok()?;
+    let field = rest.get(..len)?;
+    *s = &rest[len..];
+    Some(field)
+}
+
+/// Type names are put in the query, so they're limited to what database types look like, e.g.
+/// `timestamp with time zone` or `integer[]`.
+fn is_valid_type_name(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '.' | '[' | ']'))
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = String::new();
+        for value in &self.0 {
+            match value {
+                CursorValue::Integer(i) => {
+                    buf.push('i');
+                    push_field(&mut buf, &i.to_string());
+                }
+                CursorValue::Real(bits) => {
+                    buf.push('r');
+                    push_field(&mut buf, &f64::from_bits(*bits).to_string());
+                }
+                CursorValue::Text { type_name, value } => {
+                    buf.push('t');
+                    push_field(&mut buf, type_name);
+                    match value {
+                        Some(v) => {
+                            buf.push('v');
+                            push_field(&mut buf, v);
+                        }
+                        None => buf.push('n'),
+                    }
+                }
+            }
+        }
+        for byte in buf.as_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Take the tag of the next part of a cursor.
+fn take_tag(s: &mut &str) -> Option<u8> {
+    let tag = *s.as_bytes().first()?;
+    *s = s.get(1..)?;
+    Some(tag)
+}
+
+impl FromStr for Cursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::OrmliteError(format!("Invalid pagination cursor: {s}"));
+        if !s.len().is_multiple_of(2) {
+            return Err(invalid());
+        }
+        let bytes = (0..s.len())
+            .step_by(2)
+            .map(|i| s.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let mut rest = decoded.as_str();
+        let mut values = Vec::new();
+        while !rest.is_empty() {
+            let value = match take_tag(&mut rest) {
+                Some(b'i') => {
+                    CursorValue::Integer(take_field(&mut rest).and_then(|i| i.parse().ok()).ok_or_else(invalid)?)
+                }
+                Some(b'r') => {
+                    let real = take_field(&mut rest).and_then(|r| r.parse::<f64>().ok());
+                    CursorValue::Real(real.ok_or_else(invalid)?.to_bits())
+                }
+                Some(b't') => {
+                    let type_name = take_field(&mut rest).ok_or_else(invalid)?;
+                    if !is_valid_type_name(type_name) {
+                        return Err(invalid());
+                    }
+                    let value = match take_tag(&mut rest) {
+                        Some(b'v') => Some(take_field(&mut rest).ok_or_else(invalid)?.to_string()),
+                        Some(b'n') => None,
+                        _ => return Err(invalid()),
+                    };
+                    CursorValue::Text {
+                        type_name: type_name.to_string(),
+                        value,
+                    }
+                }
+                _ => return Err(invalid()),
+            };
+            values.push(value);
+        }
+        Ok(Cursor(values))
+    }
+}
+
+impl TryFrom<String> for Cursor {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(cursor: Cursor) -> Self {
+        cursor.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = Cursor::new(vec![
+            CursorValue::Text {
+                type_name: "timestamp with time zone".to_string(),
+                value: Some("2024-01-01 00:00:00+00:1:".to_string()),
+            },
+            CursorValue::Text {
+                type_name: "integer".to_string(),
+                value: None,
+            },
+            CursorValue::Text {
+                type_name: "".to_string(),
+                value: Some("".to_string()),
+            },
+            CursorValue::Integer(-42),
+            CursorValue::Real((0.1f64 + 0.2).to_bits()),
+        ]);
+        let s = cursor.to_string();
+        assert!(s.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(s.parse::<Cursor>().unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        assert!("zz".parse::<Cursor>().is_err());
+        assert!("abc".parse::<Cursor>().is_err());
+        let injected = Cursor::new(vec![CursorValue::Text {
+            type_name: "int); DROP TABLE users; --".to_string(),
+            value: None,
+        }]);
+        assert!(injected.to_string().parse::<Cursor>().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::error::{Error, Result};
+use crate::query_builder::args::QueryBuilderArgs;
+use crate::query_builder::lock::RowLock;
+use crate::query_builder::{DatabaseMetadata, Placeholder, util};
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use sql::Select;
+use sqlx::{Acquire, Executor, IntoArguments};
+use std::marker::PhantomData;
+
+/// A select query whose results are decoded into `T` instead of the model. Created with
+/// [`SelectQueryBuilder::select_as`](super::SelectQueryBuilder::select_as) or
+/// [`SelectQueryBuilder::into_projection`](super::SelectQueryBuilder::into_projection).
+pub struct ProjectionQueryBuilder<'args, DB, T>
+where
+    DB: sqlx::Database,
+{
+    pub query: Select,
+    arguments: QueryBuilderArgs<'args, DB>,
+    placeholder: Placeholder,
+    lock: Option<RowLock>,
+    error: Option<Error>,
+    projection: PhantomData<T>,
+}
+
+impl<'args, DB: sqlx::Database, T> ProjectionQueryBuilder<'args, DB, T> {
+    pub(super) fn new(
+        query: Select,
+        arguments: QueryBuilderArgs<'args, DB>,
+        placeholder: Placeholder,
+        lock: Option<RowLock>,
+        error: Option<Error>,
+    ) -> Self {
+        Self {
+            query,
+            arguments,
+            placeholder,
+            lock,
+            error,
+            projection: PhantomData,
+        }
+    }
+}
+
+impl<'args, DB, T> ProjectionQueryBuilder<'args, DB, T>
+where
+    T: Send + Unpin + for<'r> sqlx::FromRow<'r, DB::Row> + 'static,
+    DB: sqlx::Database + DatabaseMetadata,
+    DB::Arguments<'args>: IntoArguments<'args, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    pub fn fetch_all<'a, A>(self, db: A) -> BoxFuture<'a, Result<Vec<T>>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+    {
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let rows = util::query_as_with_recast_lifetime::<DB, T>(z, args)
+                .fetch_all(&mut *conn)
+                .await?;
+            Ok(rows)
+        })
+    }
+
+    pub fn fetch_one<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<T>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+    {
+        if self.query.limit.is_none() {
+            self.query.limit = Some(1);
+        }
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let row = util::query_as_with_recast_lifetime::<DB, T>(z, args)
+                .fetch_one(&mut *conn)
+                .await?;
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_optional<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<Option<T>>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+    {
+        if self.query.limit.is_none() {
+            self.query.limit = Some(1);
+        }
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let row = util::query_as_with_recast_lifetime::<DB, T>(z, args)
+                .fetch_optional(&mut *conn)
+                .await?;
+            Ok(row)
+        })
+    }
+
+    /// Stream the results as rows arrive, instead of loading them all into memory.
+    pub fn fetch<'a, E>(self, db: E) -> BoxStream<'a, Result<T>>
+    where
+        'args: 'a,
+        E: 'a + Executor<'a, Database = DB>,
+    {
+        let (text, args) = match self.into_query_and_args() {
+            Ok(query) => query,
+            Err(err) => return stream::once(async { Err(err) }).boxed(),
+        };
+        util::OwnedQueryStream::new(text, |text| {
+            util::query_as_with_recast_lifetime::<DB, T>(text, args)
+                .fetch(db)
+                .map_err(Error::from)
+                .boxed()
+        })
+        .boxed()
+    }
+
+    pub fn into_query_and_args(mut self) -> Result<(String, QueryBuilderArgs<'args, DB>)> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        util::build_query(&self.query, self.lock, self.arguments, &mut self.placeholder)
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, T> ProjectionQueryBuilder<'args, DB, T> {
+    /// The query as it's sent to the database, with the database's placeholders, e.g. to log it or to snapshot it in
+    /// tests. Unlike [`into_query_and_args`](Self::into_query_and_args), it keeps the builder.
+    pub fn to_sql(&self) -> Result<String> {
+        if let Some(err) = &self.error {
+            return Err(Error::OrmliteError(err.to_string()));
+        }
+        let q = util::select_text::<DB>(&self.query, self.lock)?;
+        util::replace_and_check_placeholders(&q, &[&self.arguments], &mut DB::placeholder())
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, T> std::fmt::Debug for ProjectionQueryBuilder<'args, DB, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::debug_query(f, "ProjectionQueryBuilder", self.to_sql(), &[&self.arguments])
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, T> std::fmt::Display for ProjectionQueryBuilder<'args, DB, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::fmt_query(f, self.to_sql(), &[&self.arguments])
+    }
+}
@@ -1,20 +1,53 @@
 use crate::error::{Error, Result};
-use crate::model::Model;
+use crate::model::{Model, TableMeta};
 use crate::query_builder::args::QueryBuilderArgs;
-use crate::query_builder::{Placeholder, util};
-use sql::{Expr, OrderBy, ToSql};
+use crate::query_builder::lock::{LockStrength, LockWait, RowLock};
+use crate::query_builder::page::CursorValue;
+use crate::query_builder::{Cursor, IntoCondition, Page, Placeholder, ProjectionQueryBuilder, util};
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use sql::{Expr, OrderBy};
 
 use crate::join::{JoinDescription, criteria, select_columns};
-use sql::{Select, query::Where};
-use sqlx::{Executor, IntoArguments};
+use sql::query::{Criteria, JoinType, Where};
+use sql::{Select, ToSql};
+use sqlx::{Acquire, Executor, IntoArguments, Row, TypeInfo, ValueRef};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
+// Not prefixed with `__`, which FromRow reserves for the columns of many to one joins.
+const CURSOR_VALUE: &str = "_ormlite_cursor_";
+const CURSOR_TYPE: &str = "_ormlite_cursor_type_";
+const COMPOUND: &str = "_ormlite_compound";
+/// The most ids a join loads with one query. Larger batches are split to stay under the databases' limits on bound
+/// parameters, 32766 for SQLite and 65535 for Postgres.
+const IDS_PER_QUERY: usize = 10_000;
+
 // Add additional information to the sqlx::Database
 pub trait DatabaseMetadata {
     fn dialect() -> sql::Dialect;
     fn placeholder() -> Placeholder;
 }
 
+/// The number of rows an UPDATE or DELETE changed, for the database's query result.
+pub trait RowsAffected {
+    fn rows_affected(&self) -> u64;
+}
+
+#[cfg(feature = "postgres")]
+impl RowsAffected for sqlx::postgres::PgQueryResult {
+    fn rows_affected(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl RowsAffected for sqlx::sqlite::SqliteQueryResult {
+    fn rows_affected(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl DatabaseMetadata for sqlx::postgres::Postgres {
     fn dialect() -> sql::Dialect {
@@ -45,6 +78,94 @@ where
     arguments: QueryBuilderArgs<'args, DB>,
     model: PhantomData<Model>,
     placeholder: Placeholder,
+    /// Joins that are loaded with a follow-up query once the main query has run, e.g. one to many.
+    deferred_joins: Vec<JoinDescription>,
+    /// Arguments of join conditions, keyed by join alias. They're placed before `arguments`, because
+    /// joins come before the WHERE clause in the query.
+    join_arguments: HashMap<String, QueryBuilderArgs<'args, DB>>,
+    /// Arguments of the WITH clause, which come first in the query.
+    cte_arguments: QueryBuilderArgs<'args, DB>,
+    /// Arguments of the HAVING clause, which come after the WHERE clause in the query.
+    having_arguments: QueryBuilderArgs<'args, DB>,
+    lock: Option<RowLock>,
+    /// An error from building the query, e.g. a subquery, returned when the query is built.
+    error: Option<Error>,
+    /// Which rows of a soft deleted model to select. `None` selects all of them.
+    soft_delete: Option<SoftDeleteScope>,
+}
+
+/// The rows of a soft deleted model a query selects, by its soft delete column, qualified with the table.
+enum SoftDeleteScope {
+    Live(String),
+    Deleted(String),
+}
+
+impl SoftDeleteScope {
+    fn condition(&self) -> String {
+        match self {
+            SoftDeleteScope::Live(column) => format!("{column} IS NULL"),
+            SoftDeleteScope::Deleted(column) => format!("{column} IS NOT NULL"),
+        }
+    }
+
+    /// Restrict `query` to the scope. The query's own conditions are parenthesized first, so the scope applies to
+    /// all of them, even one with an OR.
+    fn apply(&self, mut query: Select) -> Select {
+        if !query.where_.is_empty() {
+            // An OR of one condition renders as the condition in parentheses.
+            query.where_ = Where::Or(vec![std::mem::replace(&mut query.where_, Where::And(Vec::new()))]);
+        }
+        query.where_raw(self.condition())
+    }
+}
+
+/// The model's soft delete column, qualified with its table.
+fn soft_delete_column<M: TableMeta>() -> Option<String> {
+    M::soft_delete_column().map(|column| format!("\"{}\".\"{column}\"", M::table_name()))
+}
+
+/// Read the `i`th ORDER BY key of a page's last row for the next cursor.
+fn cursor_value<R: Row>(row: &R, i: usize, postgres: bool) -> Result<CursorValue>
+where
+    i64: sqlx::Type<R::Database> + for<'r> sqlx::Decode<'r, R::Database>,
+    f64: sqlx::Type<R::Database> + for<'r> sqlx::Decode<'r, R::Database>,
+    String: sqlx::Type<R::Database> + for<'r> sqlx::Decode<'r, R::Database>,
+    Option<String>: sqlx::Type<R::Database> + for<'r> sqlx::Decode<'r, R::Database>,
+    for<'s> &'s str: sqlx::ColumnIndex<R>,
+{
+    let name = format!("{CURSOR_VALUE}{i}");
+    if postgres {
+        return Ok(CursorValue::Text {
+            type_name: row.try_get(format!("{CURSOR_TYPE}{i}").as_str())?,
+            value: row.try_get(name.as_str())?,
+        });
+    }
+    let storage_class = row.try_get_raw(name.as_str())?.type_info().name().to_string();
+    match storage_class.as_str() {
+        "INTEGER" => Ok(CursorValue::Integer(row.try_get(name.as_str())?)),
+        "REAL" => Ok(CursorValue::Real(row.try_get::<f64, _>(name.as_str())?.to_bits())),
+        "TEXT" | "NULL" => Ok(CursorValue::Text {
+            type_name: String::new(),
+            value: row.try_get(name.as_str())?,
+        }),
+        other => Err(Error::OrmliteError(format!(
+            "paginate_after can't order by {other} values"
+        ))),
+    }
+}
+
+/// Bind a cursor value as the type it was read as.
+fn bind_cursor_value<'args, DB: sqlx::Database>(arguments: &mut QueryBuilderArgs<'args, DB>, value: &CursorValue)
+where
+    i64: sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    f64: sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    Option<String>: sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+{
+    match value {
+        CursorValue::Integer(i) => arguments.add(*i),
+        CursorValue::Real(bits) => arguments.add(f64::from_bits(*bits)),
+        CursorValue::Text { value, .. } => arguments.add(value.clone()),
+    }
 }
 
 impl<'args, DB, M> SelectQueryBuilder<'args, DB, M>
@@ -52,47 +173,305 @@ where
     M: Sized + Send + Sync + Unpin + for<'r> sqlx::FromRow<'r, DB::Row> + 'static + Model<DB>,
     DB: sqlx::Database + DatabaseMetadata,
     DB::Arguments<'args>: IntoArguments<'args, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
 {
-    pub async fn fetch_all<'executor, E>(self, db: E) -> Result<Vec<M>>
+    pub fn fetch_all<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<Vec<M>>>
     where
-        E: Executor<'executor, Database = DB>,
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
     {
-        let (text, args) = self.into_query_and_args()?;
-        let z: &str = &text;
-        util::query_as_with_recast_lifetime::<DB, M>(z, args)
-            .fetch_all(db)
-            .await
-            .map_err(Error::from)
+        let joins = std::mem::take(&mut self.deferred_joins);
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let mut models = util::query_as_with_recast_lifetime::<DB, M>(z, args)
+                .fetch_all(&mut *conn)
+                .await?;
+            for join in joins {
+                M::_load_join(&mut models, join, &mut *conn).await?;
+            }
+            Ok(models)
+        })
     }
 
-    pub async fn fetch_one<'executor, E>(mut self, db: E) -> Result<M>
+    pub fn fetch_one<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<M>>
     where
-        E: Executor<'executor, Database = DB>,
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
     {
         if self.query.limit.is_none() {
             self.query.limit = Some(1);
         }
-        let (text, args) = self.into_query_and_args()?;
-        let z: &str = &text;
-        util::query_as_with_recast_lifetime::<DB, M>(z, args)
-            .fetch_one(db)
-            .await
-            .map_err(Error::from)
+        let joins = std::mem::take(&mut self.deferred_joins);
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let mut model = util::query_as_with_recast_lifetime::<DB, M>(z, args)
+                .fetch_one(&mut *conn)
+                .await?;
+            for join in joins {
+                M::_load_join(std::slice::from_mut(&mut model), join, &mut *conn).await?;
+            }
+            Ok(model)
+        })
     }
 
-    pub async fn fetch_optional<'executor, E>(mut self, db: E) -> Result<Option<M>>
+    pub fn fetch_optional<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<Option<M>>>
     where
-        E: Executor<'executor, Database = DB>,
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
     {
         if self.query.limit.is_none() {
             self.query.limit = Some(1);
         }
-        let (text, args) = self.into_query_and_args()?;
-        let z: &str = &text;
-        util::query_as_with_recast_lifetime::<DB, M>(z, args)
-            .fetch_optional(db)
-            .await
-            .map_err(Error::from)
+        let joins = std::mem::take(&mut self.deferred_joins);
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let mut model = util::query_as_with_recast_lifetime::<DB, M>(z, args)
+                .fetch_optional(&mut *conn)
+                .await?;
+            if let Some(model) = &mut model {
+                for join in joins {
+                    M::_load_join(std::slice::from_mut(model), join, &mut *conn).await?;
+                }
+            }
+            Ok(model)
+        })
+    }
+
+    /// Stream the models as rows arrive, instead of loading them all into memory.
+    ///
+    /// Joins loaded with a follow-up query (one to many and many to many) need every model, so they can't
+    /// be streamed. The stream yields an error if there are any.
+    pub fn fetch<'a, E>(self, db: E) -> BoxStream<'a, Result<M>>
+    where
+        'args: 'a,
+        E: 'a + Executor<'a, Database = DB>,
+    {
+        if let Some(join) = self.deferred_joins.first() {
+            let err = Error::OrmliteError(format!(
+                "`{}` is loaded with a separate query, which isn't supported when streaming",
+                join.field()
+            ));
+            return stream::once(async { Err(err) }).boxed();
+        }
+        let (text, args) = match self.into_query_and_args() {
+            Ok(query) => query,
+            Err(err) => return stream::once(async { Err(err) }).boxed(),
+        };
+        util::OwnedQueryStream::new(text, |text| {
+            util::query_as_with_recast_lifetime::<DB, M>(text, args)
+                .fetch(db)
+                .map_err(Error::from)
+                .boxed()
+        })
+        .boxed()
+    }
+
+    /// Fetch a page of `page_size` models, starting after `cursor`, or from the start if it's `None`.
+    ///
+    /// Pages are keyed on the query's ORDER BY (set with [`order_asc`](Self::order_asc) and
+    /// [`order_desc`](Self::order_desc)), so unlike OFFSET, later pages are as fast as the first. Keys can mix
+    /// directions. The order must be unique, e.g. by ending with the primary key, and its columns non-null.
+    pub fn paginate_after<'a, A>(
+        mut self,
+        cursor: Option<&Cursor>,
+        page_size: usize,
+        db: A,
+    ) -> BoxFuture<'a, Result<Page<M>>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        i64: sqlx::Type<DB> + sqlx::Encode<'args, DB> + for<'r> sqlx::Decode<'r, DB>,
+        f64: sqlx::Type<DB> + sqlx::Encode<'args, DB> + for<'r> sqlx::Decode<'r, DB>,
+        String: sqlx::Type<DB> + for<'r> sqlx::Decode<'r, DB>,
+        Option<String>: sqlx::Type<DB> + sqlx::Encode<'args, DB> + for<'r> sqlx::Decode<'r, DB>,
+        for<'s> &'s str: sqlx::ColumnIndex<DB::Row>,
+    {
+        let joins = std::mem::take(&mut self.deferred_joins);
+        let keys = self.query.order.clone();
+        let postgres = matches!(DB::dialect(), sql::Dialect::Postgres);
+        let query = self.into_page_query(&keys, cursor, page_size, postgres);
+        Box::pin(async move {
+            let (text, args) = query?;
+            let mut conn = db.acquire().await?;
+            let rows = util::query_with_recast_lifetime::<DB>(&text, args)
+                .fetch_all(&mut *conn)
+                .await?;
+            let next_cursor = match rows.len() > page_size && page_size > 0 {
+                true => {
+                    let last = &rows[page_size - 1];
+                    let values = (0..keys.len())
+                        .map(|i| cursor_value(last, i, postgres))
+                        .collect::<Result<Vec<_>>>()?;
+                    Some(Cursor::new(values))
+                }
+                false => None,
+            };
+            let mut items = rows
+                .iter()
+                .take(page_size)
+                .map(|row| M::from_row(row))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            for join in joins {
+                M::_load_join(&mut items, join, &mut *conn).await?;
+            }
+            Ok(Page { items, next_cursor })
+        })
+    }
+
+    /// The query for a page: filtered to after the cursor, selecting the ORDER BY keys for the next cursor, and
+    /// fetching one more model than the page size to tell whether there's a next page.
+    fn into_page_query(
+        mut self,
+        keys: &[OrderBy],
+        cursor: Option<&Cursor>,
+        page_size: usize,
+        postgres: bool,
+    ) -> Result<(String, QueryBuilderArgs<'args, DB>)>
+    where
+        i64: sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+        f64: sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+        Option<String>: sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        if keys.is_empty() {
+            return Err(Error::OrmliteError("paginate_after requires an ORDER BY".to_string()));
+        }
+        if let Some(cursor) = cursor {
+            let values = cursor.values();
+            if values.len() != keys.len() {
+                return Err(Error::OrmliteError(
+                    "The pagination cursor doesn't match the query's ORDER BY".to_string(),
+                ));
+            }
+            let placeholder = |i: usize| match &values[i] {
+                CursorValue::Text { type_name, .. } if postgres => format!("CAST(? AS {type_name})"),
+                _ => "?".to_string(),
+            };
+            // (a > ?) OR (a = ? AND b > ?) OR ..., with < for descending keys
+            let mut clauses = Vec::new();
+            for (i, key) in keys.iter().enumerate() {
+                let mut clause = Vec::new();
+                for (j, prior) in keys[..i].iter().enumerate() {
+                    clause.push(format!("{} = {}", prior.column, placeholder(j)));
+                    bind_cursor_value(&mut self.arguments, &values[j]);
+                }
+                let op = match key.direction {
+                    Some(sql::query::Direction::Desc) => "<",
+                    _ => ">",
+                };
+                clause.push(format!("{} {op} {}", key.column, placeholder(i)));
+                bind_cursor_value(&mut self.arguments, &values[i]);
+                clauses.push(format!("({})", clause.join(" AND ")));
+            }
+            self.query = self.query.where_raw(format!("({})", clauses.join(" OR ")));
+        }
+        for (i, key) in keys.iter().enumerate() {
+            // SQLite keys are read back with their storage class, Postgres keys as text with their type.
+            if postgres {
+                self.query = self
+                    .query
+                    .select_raw(format!("CAST({} AS TEXT) AS \"{CURSOR_VALUE}{i}\"", key.column))
+                    .select_raw(format!(
+                        "CAST(pg_typeof({}) AS TEXT) AS \"{CURSOR_TYPE}{i}\"",
+                        key.column
+                    ));
+            } else {
+                self.query = self
+                    .query
+                    .select_raw(format!("{} AS \"{CURSOR_VALUE}{i}\"", key.column));
+            }
+        }
+        self.query.limit = Some(page_size + 1);
+        self.into_query_and_args()
+    }
+
+    /// Count the models matching the query. ORDER BY, LIMIT and OFFSET are ignored.
+    pub fn count<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<i64>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        i64: sqlx::Type<DB> + for<'r> sqlx::Decode<'r, DB>,
+        usize: sqlx::ColumnIndex<DB::Row>,
+    {
+        self.clear_ordering();
+        // Distinct and grouped rows have to be counted after they're built, by the query as it is, because its
+        // HAVING clause may refer to its columns.
+        let wrap = self.query.distinct || !self.query.group.is_empty();
+        if !wrap {
+            self.query.columns = vec![sql::query::SelectColumn::raw("COUNT(*)")];
+        }
+        let query = self.into_query_and_args().map(|(text, args)| {
+            if wrap {
+                (format!("SELECT COUNT(*) FROM ({text}) AS \"_ormlite_count\""), args)
+            } else {
+                (text, args)
+            }
+        });
+        Self::fetch_scalar(query, db)
+    }
+
+    /// Whether any model matches the query. ORDER BY, LIMIT and OFFSET are ignored.
+    pub fn exists<'a, A>(mut self, db: A) -> BoxFuture<'a, Result<bool>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        bool: sqlx::Type<DB> + for<'r> sqlx::Decode<'r, DB>,
+        usize: sqlx::ColumnIndex<DB::Row>,
+    {
+        self.clear_ordering();
+        self.query.columns = vec![sql::query::SelectColumn::raw("1")];
+        let query = self
+            .into_query_and_args()
+            .map(|(text, args)| (format!("SELECT EXISTS ({text})"), args));
+        Self::fetch_scalar(query, db)
+    }
+
+    /// Compute an aggregate over the models matching the query. ORDER BY, LIMIT and OFFSET are ignored.
+    /// If the query has a GROUP BY, the value of the first group is returned.
+    ///
+    /// # Arguments
+    /// * `expression` - The aggregate to select. Examples: "MAX(created_at)", "SUM(amount)"
+    pub fn aggregate<'a, T, A>(mut self, expression: &str, db: A) -> BoxFuture<'a, Result<T>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        T: 'a + Send + Unpin + sqlx::Type<DB> + for<'r> sqlx::Decode<'r, DB>,
+        usize: sqlx::ColumnIndex<DB::Row>,
+    {
+        self.clear_ordering();
+        self.query.columns = vec![sql::query::SelectColumn::raw(expression)];
+        Self::fetch_scalar(self.into_query_and_args(), db)
+    }
+
+    /// Drop what doesn't apply to an aggregate over the whole query: ordering, paging and row locks.
+    fn clear_ordering(&mut self) {
+        self.query.order.clear();
+        self.query.limit = None;
+        self.query.offset = None;
+        self.lock = None;
+    }
+
+    fn fetch_scalar<'a, T, A>(query: Result<(String, QueryBuilderArgs<'args, DB>)>, db: A) -> BoxFuture<'a, Result<T>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        T: 'a + Send + Unpin + sqlx::Type<DB> + for<'r> sqlx::Decode<'r, DB>,
+        usize: sqlx::ColumnIndex<DB::Row>,
+    {
+        Box::pin(async move {
+            let (text, args) = query?;
+            let mut conn = db.acquire().await?;
+            let z: &str = &text;
+            let (value,) = util::query_as_with_recast_lifetime::<DB, (T,)>(z, args)
+                .fetch_one(&mut *conn)
+                .await?;
+            Ok(value)
+        })
     }
 
     pub fn with(mut self, name: &str, query: &str) -> Self {
@@ -100,6 +479,71 @@ where
         self
     }
 
+    /// Add a common table expression built from another query, e.g.
+    /// `.with_query("recent", Post::select().where_bind("created_at > ?", since))`. Its arguments are placed
+    /// before the rest of the query's.
+    pub fn with_query<T: Model<DB>>(mut self, name: &str, query: SelectQueryBuilder<'args, DB, T>) -> Self {
+        if let Some((text, args)) = self.add_subquery(query) {
+            self.query = self.query.with_raw(name, &text);
+            self.cte_arguments.extend(args);
+        }
+        self
+    }
+
+    /// Combine with the models of `other`, removing duplicates, with `UNION`. The result is a query of the
+    /// combined models, so filters, ORDER BY, and LIMIT added afterwards apply to all of them, e.g.
+    /// `owned.union(shared).order_desc("updated_at").limit(20)`.
+    pub fn union(self, other: Self) -> Self {
+        self.compound("UNION", other)
+    }
+
+    /// Like [`union`](Self::union), but keeps duplicates, with `UNION ALL`.
+    pub fn union_all(self, other: Self) -> Self {
+        self.compound("UNION ALL", other)
+    }
+
+    /// Like [`union`](Self::union), but keeps only the models that are also in `other`, with `INTERSECT`.
+    pub fn intersect(self, other: Self) -> Self {
+        self.compound("INTERSECT", other)
+    }
+
+    /// Like [`union`](Self::union), but removes the models that are in `other`, with `EXCEPT`.
+    pub fn except(self, other: Self) -> Self {
+        self.compound("EXCEPT", other)
+    }
+
+    /// Select the models of `self <operator> other`, from a CTE aliased to the model's table, so the result can be
+    /// filtered and joined like the table.
+    fn compound(self, operator: &str, other: Self) -> Self {
+        let table = M::table_name();
+        let mut combined = Self::default().select(format!("\"{table}\".*"));
+        // Each side already skips soft deleted models, unless it asked for them.
+        combined.soft_delete = None;
+        let mut arms = Vec::new();
+        for query in [self, other] {
+            // Each side of a compound query can't have its own WITH, ORDER BY, LIMIT or lock, unless it's nested.
+            let nested = !query.query.ctes.is_empty()
+                || !query.query.order.is_empty()
+                || query.query.limit.is_some()
+                || query.query.offset.is_some()
+                || query.lock.is_some();
+            if let Some((text, args)) = combined.add_subquery(query) {
+                arms.push(match nested {
+                    true => format!("SELECT * FROM ({text}) AS \"{COMPOUND}_{}\"", arms.len()),
+                    false => text,
+                });
+                combined.cte_arguments.extend(args);
+            }
+        }
+        combined.query = combined.query.with_raw(COMPOUND, &arms.join(&format!(" {operator} ")));
+        combined.query.from = Some(sql::query::From {
+            schema: None,
+            table: COMPOUND.to_string(),
+            alias: Some(table.to_string()),
+        });
+        combined
+    }
+
     /// Add a column to the query. Note you typically don't need this, as creating a query from
     /// `Model::select` will automatically add that model's columns.
     ///
@@ -121,22 +565,58 @@ where
     /// still want to re-use parameters, you can use $<N> placeholders. However, don't mix `?` and
     /// `$<N>` placeholders, as they will conflict.
     ///
+    /// Instead of a clause, you can pass a condition built from the model's typed columns, which carries its own
+    /// arguments, e.g. `User::columns().age.gt(18)`.
+    ///
     /// # Arguments
     /// * `clause` - The clause to add. Examples: "id = ?", "name = ?", "person.id = ?"
-    pub fn where_(mut self, clause: &'static str) -> Self {
-        self.query = self.query.where_raw(clause);
+    pub fn where_(mut self, clause: impl IntoCondition<'args, DB>) -> Self {
+        let (where_, args) = clause.into_condition().into_parts();
+        self.query = self.query.where_(where_);
+        self.arguments.extend(args);
         self
     }
 
     /// Convenience method to add a `WHERE` and bind a value in one call.
     pub fn where_bind<T>(mut self, clause: &'static str, value: T) -> Self
     where
-        T: 'args + Send + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
     {
         self.query = self.query.where_raw(clause);
         self.arguments.add(value);
         self
     }
+    /// Add a `WHERE <column> IN (...)` clause, with a placeholder for each value. No values match no rows.
+    ///
+    /// # Arguments
+    /// * `column` - The column to match. Examples: "id", "person.id"
+    pub fn where_in<T>(mut self, column: &'static str, values: impl IntoIterator<Item = T>) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.query = self.query.where_raw(format!("{column} IN (?)"));
+        self.arguments.add_list(values);
+        self
+    }
+
+    /// Add a `WHERE <column> IN (<query>)` clause, with the arguments of `query` placed where the clause is.
+    /// `query` should select a single column, e.g. with [`clear_select`](Self::clear_select) and
+    /// [`select`](Self::select).
+    ///
+    /// # Arguments
+    /// * `column` - The column to match. Examples: "id", "person.id"
+    pub fn where_in_subquery<T: Model<DB>>(
+        mut self,
+        column: &'static str,
+        query: SelectQueryBuilder<'args, DB, T>,
+    ) -> Self {
+        if let Some((text, args)) = self.add_subquery(query) {
+            self.query = self.query.where_raw(format!("{column} IN ({text})"));
+            self.arguments.extend(args);
+        }
+        self
+    }
+
     /// Dangerous because it takes a string that could be user crafted. You should prefer `.where_` which
     /// takes a &'static str, and pass arguments with `.bind()`.
     pub fn dangerous_where(mut self, clause: &str) -> Self {
@@ -144,31 +624,162 @@ where
         self
     }
 
+    /// Include soft deleted models, which are skipped by default. Does nothing if the model isn't soft deleted.
+    /// Models joined to these ones still skip their soft deleted rows.
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = None;
+        self
+    }
+
+    /// Select only soft deleted models. Building the query fails if the model isn't soft deleted.
+    pub fn only_deleted(mut self) -> Self {
+        match soft_delete_column::<M>() {
+            Some(column) => self.soft_delete = Some(SoftDeleteScope::Deleted(column)),
+            None => {
+                self.error.get_or_insert(Error::OrmliteError(format!(
+                    "{} has no soft delete column",
+                    M::table_name()
+                )));
+            }
+        }
+        self
+    }
+
+    /// Load a relation along with the models. Many to one joins become a `LEFT JOIN` on the query.
+    /// One to many and many to many joins are loaded with a second query once this query has run.
+    ///
+    /// Joins of joins, e.g. `User::organization().then(Organization::owner())`, become a chain of `LEFT JOIN`s.
+    /// Only many to one joins can be nested.
     pub fn join(mut self, join_description: JoinDescription) -> Self {
         match &join_description {
-            JoinDescription::ManyToOne {
-                columns,
-                foreign_table,
-                field,
-                foreign_key,
-                local_column,
-            } => {
-                let join = sql::query::Join {
-                    typ: sql::query::JoinType::Left,
-                    table: sql::query::JoinTable::Table {
-                        schema: None,
-                        table: foreign_table.to_string(),
-                    },
-                    alias: Some(field.to_string()),
-                    criteria: criteria(M::table_name(), local_column, field, foreign_key),
-                };
-                self.query.join.push(join);
-                self.query.columns.extend(select_columns(columns, field))
+            JoinDescription::OneToMany { .. } | JoinDescription::ManyToMany { .. } => {
+                self.deferred_joins.push(join_description)
+            }
+            _ => {
+                self.join_path(&join_description, JoinType::Left);
             }
         }
         self
     }
 
+    /// Like [`join`](Self::join), but with an `INNER JOIN`, so models without the relation are not returned.
+    /// Only many to one joins can be inner joined. Building the query fails for other joins.
+    pub fn inner_join(mut self, join_description: JoinDescription) -> Self {
+        self.join_path(&join_description, JoinType::Inner);
+        self
+    }
+
+    /// Add a condition to the `ON` clause of a many to one join, joining it first if needed. If the condition
+    /// doesn't hold, a `LEFT JOIN` leaves the relation unloaded, and an `INNER JOIN` filters out the model.
+    /// Building the query fails for other joins.
+    ///
+    /// The joined table is aliased to the field name, or the field names of a nested join separated by `__`.
+    /// Use [`join_on_bind`](Self::join_on_bind) to bind arguments to the condition.
+    ///
+    /// # Arguments
+    /// * `clause` - The condition to add. Examples: "organization.archived_at IS NULL"
+    pub fn join_on(mut self, join_description: JoinDescription, clause: &'static str) -> Self {
+        if let Some(alias) = self.join_path(&join_description, JoinType::Left) {
+            self.add_join_condition(&alias, clause);
+        }
+        self
+    }
+
+    /// Convenience method to add a condition to the `ON` clause of a join and bind a value in one call.
+    /// Unlike [`bind`](Self::bind), the value is placed with the join, before the arguments of the WHERE clause.
+    pub fn join_on_bind<T>(mut self, join_description: JoinDescription, clause: &'static str, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        if let Some(alias) = self.join_path(&join_description, JoinType::Left) {
+            self.add_join_condition(&alias, clause);
+            self.join_arguments.entry(alias).or_default().add(value);
+        }
+        self
+    }
+
+    /// Join every many to one join of the path, and return the alias of the last one. If the path has another
+    /// kind of join, nothing is joined, and the error is kept to return when the query is built.
+    fn join_path(&mut self, join_description: &JoinDescription, typ: JoinType) -> Option<String> {
+        let path = match join_description {
+            JoinDescription::Nested(path) => path.as_slice(),
+            join => std::slice::from_ref(join),
+        };
+        if let Some(join) = path.iter().find(|j| !matches!(j, JoinDescription::ManyToOne { .. })) {
+            self.error.get_or_insert(Error::OrmliteError(format!(
+                "Only many to one joins can be nested or filtered, but `{}` is not one",
+                join.field()
+            )));
+            return None;
+        }
+        let mut table = M::table_name().to_string();
+        let mut prefix = String::new();
+        for join in path {
+            table = self.join_many_to_one(join, &table, &prefix, typ);
+            prefix = format!("{table}__");
+        }
+        Some(table)
+    }
+
+    /// Join the table of a many to one join, aliased to `<prefix><field>`, and select its columns as
+    /// `__<prefix><field>__<column>`. Returns the alias. If the alias is already joined, an inner join
+    /// replaces a left join.
+    fn join_many_to_one(&mut self, join: &JoinDescription, local_table: &str, prefix: &str, typ: JoinType) -> String {
+        let JoinDescription::ManyToOne {
+            columns,
+            foreign_table,
+            field,
+            foreign_key,
+            local_column,
+            soft_delete_column,
+        } = join
+        else {
+            unreachable!("join_many_to_one called with {join:?}");
+        };
+        let alias = format!("{prefix}{field}");
+        if let Some(existing) = self
+            .query
+            .join
+            .iter_mut()
+            .find(|j| j.alias.as_deref() == Some(alias.as_str()))
+        {
+            if typ == JoinType::Inner {
+                existing.typ = JoinType::Inner;
+            }
+            return alias;
+        }
+        self.query.join.push(sql::query::Join {
+            typ,
+            table: sql::query::JoinTable::Table {
+                schema: None,
+                table: foreign_table.to_string(),
+            },
+            alias: Some(alias.clone()),
+            criteria: criteria(local_table, local_column, &alias, foreign_key),
+        });
+        // Like models loaded with a second query, soft deleted models aren't joined.
+        if let Some(column) = soft_delete_column {
+            self.add_join_condition(&alias, &format!("\"{alias}\".\"{column}\" IS NULL"));
+        }
+        self.query.columns.extend(select_columns(columns, &alias));
+        alias
+    }
+
+    fn add_join_condition(&mut self, alias: &str, clause: &str) {
+        let join = self
+            .query
+            .join
+            .iter_mut()
+            .find(|j| j.alias.as_deref() == Some(alias))
+            .expect("join was just added");
+        let condition = Where::raw(clause);
+        match &mut join.criteria {
+            Criteria::On(Where::And(v)) => v.push(condition),
+            Criteria::On(w) => *w = Where::And(vec![std::mem::replace(w, Where::And(Vec::new())), condition]),
+            Criteria::Using(_) => unreachable!("joins of models are always ON"),
+        }
+    }
+
     #[doc(hidden)]
     #[deprecated(note = "Please use `where_` instead")]
     pub fn filter(self, clause: &'static str) -> Self {
@@ -181,6 +792,17 @@ where
         self
     }
 
+    /// Convenience method to add a `HAVING` clause and bind a value in one call. Unlike [`bind`](Self::bind),
+    /// the value is placed with the HAVING clause, after the arguments of the WHERE clause.
+    pub fn having_bind<T>(mut self, clause: &'static str, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.query = self.query.having(Where::raw(clause));
+        self.having_arguments.add(value);
+        self
+    }
+
     /// Add a GROUP BY clause to the query.
     ///
     /// # Arguments:
@@ -222,28 +844,301 @@ where
         self
     }
 
+    /// Lock the selected rows against updates by other transactions, with `FOR UPDATE`, until this transaction ends.
+    /// SQLite doesn't support row locks, so the query fails there.
+    pub fn for_update(mut self) -> Self {
+        self.lock_mut().strength = Some(LockStrength::Update);
+        self
+    }
+
+    /// Lock the selected rows against updates by other transactions, with `FOR SHARE`, while letting them
+    /// share the lock. SQLite doesn't support row locks, so the query fails there.
+    pub fn for_share(mut self) -> Self {
+        self.lock_mut().strength = Some(LockStrength::Share);
+        self
+    }
+
+    /// Skip rows that are already locked instead of waiting for them, e.g. to pull jobs off a queue.
+    /// Requires [`for_update`](Self::for_update) or [`for_share`](Self::for_share).
+    pub fn skip_locked(mut self) -> Self {
+        self.lock_mut().wait = LockWait::SkipLocked;
+        self
+    }
+
+    /// Fail instead of waiting if a row is already locked.
+    /// Requires [`for_update`](Self::for_update) or [`for_share`](Self::for_share).
+    pub fn nowait(mut self) -> Self {
+        self.lock_mut().wait = LockWait::NoWait;
+        self
+    }
+
+    fn lock_mut(&mut self) -> &mut RowLock {
+        self.lock.get_or_insert(RowLock {
+            strength: None,
+            wait: LockWait::Wait,
+        })
+    }
+
     /// Bind an argument to the query.
     pub fn bind<T>(mut self, value: T) -> Self
     where
-        T: 'args + Send + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
     {
         self.arguments.add(value);
         self
     }
 
-    pub fn into_query_and_args(mut self) -> Result<(String, QueryBuilderArgs<'args, DB>)> {
-        let q = self.query.to_sql(DB::dialect());
-        let args = self.arguments;
-        let (q, placeholder_count) = util::replace_placeholders(&q, &mut self.placeholder)?;
-        if placeholder_count != args.len() {
+    /// Bind a list of arguments to a single `?`, which expands to a placeholder for each value, e.g.
+    /// `.where_("id IN (?)").bind_list(ids)`. An empty list expands to a subquery without rows, so `IN (?)` matches no
+    /// rows and `NOT IN (?)` matches all of them.
+    pub fn bind_list<T>(mut self, values: impl IntoIterator<Item = T>) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.arguments.add_list(values);
+        self
+    }
+
+    /// Fetch the models whose `column` is one of `ids`, e.g. the models of a one to many join.
+    #[doc(hidden)]
+    pub async fn _fetch_where_in<K>(column: &str, ids: Vec<K>, conn: &mut DB::Connection) -> Result<Vec<M>>
+    where
+        K: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        let column = format!("\"{}\".\"{column}\"", M::table_name());
+        let rows = Self::fetch_rows_in(&column, ids, conn, |q| q).await?;
+        rows.iter().map(|row| M::from_row(row).map_err(Error::from)).collect()
+    }
+
+    /// Fetch the models related to `ids` through the join table of a many to many join. Each model is
+    /// returned with the id it is related to, so a model related to several ids is returned once per id.
+    #[doc(hidden)]
+    pub async fn _fetch_all_through<K>(
+        join: JoinDescription,
+        ids: Vec<K>,
+        conn: &mut DB::Connection,
+    ) -> Result<Vec<(K, M)>>
+    where
+        K: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB> + for<'r> sqlx::Decode<'r, DB>,
+        for<'s> &'s str: sqlx::ColumnIndex<DB::Row>,
+    {
+        let JoinDescription::ManyToMany {
+            foreign_table,
+            foreign_key,
+            join_table,
+            join_table_local_key,
+            join_table_foreign_key,
+            ..
+        } = join
+        else {
             return Err(Error::OrmliteError(format!(
-                "Failing to build query. {} placeholders were found in the query, but \
-                {} arguments were provided.",
-                placeholder_count,
-                args.len(),
+                "`{}` is not a many to many join",
+                join.field()
+            )));
+        };
+        // Not prefixed with `__`, which FromRow reserves for the columns of many to one joins.
+        const JOIN_KEY: &str = "_ormlite_join_key";
+        let column = format!("\"{join_table}\".\"{join_table_local_key}\"");
+        let rows = Self::fetch_rows_in(&column, ids, conn, |mut q| {
+            q.query.join.push(sql::query::Join {
+                typ: sql::query::JoinType::Inner,
+                table: sql::query::JoinTable::Table {
+                    schema: None,
+                    table: join_table.to_string(),
+                },
+                alias: None,
+                criteria: criteria(join_table, join_table_foreign_key, foreign_table, foreign_key),
+            });
+            q.select(format!("{column} AS \"{JOIN_KEY}\""))
+        })
+        .await?;
+        rows.iter()
+            .map(|row| Ok((row.try_get(JOIN_KEY)?, M::from_row(row)?)))
+            .collect()
+    }
+
+    /// Fetch the models whose `column` is one of `ids`, returning one entry per id, or `None` if no
+    /// model matched. Each distinct id is queried once, but a model is decoded for every id that refers
+    /// to it, so models don't need to be `Clone`.
+    #[doc(hidden)]
+    pub async fn _fetch_for_ids<K>(column: &str, ids: Vec<K>, conn: &mut DB::Connection) -> Result<Vec<Option<M>>>
+    where
+        K: 'args
+            + Clone
+            + Eq
+            + std::hash::Hash
+            + Send
+            + std::fmt::Debug
+            + sqlx::Type<DB>
+            + sqlx::Encode<'args, DB>
+            + for<'r> sqlx::Decode<'r, DB>,
+        for<'s> &'s str: sqlx::ColumnIndex<DB::Row>,
+    {
+        let mut distinct = ids.clone();
+        let mut seen = std::collections::HashSet::new();
+        distinct.retain(|id| seen.insert(id.clone()));
+        let qualified = format!("\"{}\".\"{column}\"", M::table_name());
+        let rows = Self::fetch_rows_in(&qualified, distinct, conn, |q| q).await?;
+        let mut rows_by_id = std::collections::HashMap::new();
+        for row in &rows {
+            rows_by_id.insert(row.try_get::<K, _>(column)?, row);
+        }
+        ids.iter()
+            .map(|id| rows_by_id.get(id).map(|&row| M::from_row(row)).transpose())
+            .map(|model| model.map_err(Error::from))
+            .collect()
+    }
+
+    /// Fetch the rows where `column` is one of `ids`, with a query for every [`IDS_PER_QUERY`] ids. `build` adds to
+    /// each query, e.g. joins and columns.
+    async fn fetch_rows_in<K>(
+        column: &str,
+        ids: Vec<K>,
+        conn: &mut DB::Connection,
+        build: impl Fn(Self) -> Self,
+    ) -> Result<Vec<DB::Row>>
+    where
+        K: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        let mut rows = Vec::new();
+        let mut ids = ids.into_iter().peekable();
+        while ids.peek().is_some() {
+            let query = build(M::select())
+                .dangerous_where(&format!("{column} IN (?)"))
+                .bind_list(ids.by_ref().take(IDS_PER_QUERY));
+            let (text, args) = query.into_query_and_args()?;
+            rows.extend(
+                util::query_with_recast_lifetime::<DB>(&text, args)
+                    .fetch_all(&mut *conn)
+                    .await?,
+            );
+        }
+        Ok(rows)
+    }
+
+    /// Decode the results into `T` instead of the model, selecting only `T`'s columns from the model's table.
+    /// The query keeps its filters and joins, but one to many and many to many joins can't be loaded into `T`.
+    pub fn select_as<T>(mut self) -> ProjectionQueryBuilder<'args, DB, T>
+    where
+        T: TableMeta + for<'r> sqlx::FromRow<'r, DB::Row>,
+    {
+        let table = M::table_name();
+        self.query.columns = T::table_columns()
+            .iter()
+            .map(|c| sql::query::SelectColumn::raw(&format!("\"{table}\".\"{c}\"")))
+            .collect();
+        self.into_projection()
+    }
+
+    /// Like [`select_as`](Self::select_as), but keeps the selected columns, so `T` can include computed columns
+    /// added with [`select`](Self::select). Use [`clear_select`](Self::clear_select) to remove the model's columns.
+    pub fn into_projection<T>(mut self) -> ProjectionQueryBuilder<'args, DB, T>
+    where
+        T: for<'r> sqlx::FromRow<'r, DB::Row>,
+    {
+        if let Some(join) = self.deferred_joins.first() {
+            self.error.get_or_insert(Error::OrmliteError(format!(
+                "`{}` is loaded with a separate query, which can't be decoded into a projection",
+                join.field()
             )));
         }
-        Ok((q, args))
+        self.apply_soft_delete();
+        let arguments = self.take_arguments();
+        ProjectionQueryBuilder::new(self.query, arguments, self.placeholder, self.lock, self.error)
+    }
+
+    /// Remove all selected columns, including the model's, e.g. to select only computed columns.
+    pub fn clear_select(mut self) -> Self {
+        self.query.columns.clear();
+        self
+    }
+
+    pub fn into_query_and_args(mut self) -> Result<(String, QueryBuilderArgs<'args, DB>)> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.apply_soft_delete();
+        let args = self.take_arguments();
+        util::build_query(&self.query, self.lock, args, &mut self.placeholder)
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M> SelectQueryBuilder<'args, DB, M> {
+    /// The query as it's sent to the database, with the database's placeholders, e.g. to log it or to snapshot it in
+    /// tests. Unlike [`into_query_and_args`](Self::into_query_and_args), it keeps the builder.
+    pub fn to_sql(&self) -> Result<String> {
+        if let Some(err) = &self.error {
+            return Err(Error::OrmliteError(err.to_string()));
+        }
+        let q = match &self.soft_delete {
+            Some(scope) => util::select_text::<DB>(&scope.apply(self.query.clone()), self.lock)?,
+            None => util::select_text::<DB>(&self.query, self.lock)?,
+        };
+        util::replace_and_check_placeholders(&q, &self.arguments_in_order(), &mut DB::placeholder())
+    }
+
+    /// The parts of the arguments in placeholder order, like [`take_arguments`](Self::take_arguments).
+    fn arguments_in_order(&self) -> Vec<&QueryBuilderArgs<'args, DB>> {
+        let mut args = vec![&self.cte_arguments];
+        args.extend(
+            self.query
+                .join
+                .iter()
+                .filter_map(|join| join.alias.as_ref().and_then(|a| self.join_arguments.get(a))),
+        );
+        args.push(&self.arguments);
+        args.push(&self.having_arguments);
+        args
+    }
+
+    /// The arguments of the query in placeholder order: the WITH clause, join conditions, the WHERE clause,
+    /// then the HAVING clause.
+    fn take_arguments(&mut self) -> QueryBuilderArgs<'args, DB> {
+        let mut args = std::mem::take(&mut self.cte_arguments);
+        for join in &self.query.join {
+            if let Some(join_args) = join.alias.as_ref().and_then(|a| self.join_arguments.remove(a)) {
+                args.extend(join_args);
+            }
+        }
+        args.extend(std::mem::take(&mut self.arguments));
+        args.extend(std::mem::take(&mut self.having_arguments));
+        args
+    }
+
+    /// Filter the query to the rows its soft delete scope selects.
+    fn apply_soft_delete(&mut self) {
+        if let Some(scope) = self.soft_delete.take() {
+            self.query = scope.apply(std::mem::take(&mut self.query));
+        }
+    }
+
+    /// Render `query` to nest in this one, keeping its `?` placeholders, so they're numbered along with this
+    /// query's. If it can't be rendered, the error is kept to return when this query is built.
+    fn add_subquery<T>(
+        &mut self,
+        mut query: SelectQueryBuilder<'args, DB, T>,
+    ) -> Option<(String, QueryBuilderArgs<'args, DB>)> {
+        if let Some(join) = query.deferred_joins.first() {
+            query.error.get_or_insert(Error::OrmliteError(format!(
+                "`{}` is loaded with a separate query, which can't be part of a subquery",
+                join.field()
+            )));
+        }
+        let lock = match query.error.take() {
+            Some(err) => Err(err),
+            None => query.lock.map(|lock| lock.to_sql(DB::dialect())).transpose(),
+        };
+        match lock {
+            Ok(lock) => {
+                query.apply_soft_delete();
+                let text = query.query.to_sql(DB::dialect()) + lock.as_deref().unwrap_or("");
+                Some((text, query.take_arguments()))
+            }
+            Err(err) => {
+                self.error.get_or_insert(err);
+                None
+            }
+        }
     }
 }
 
@@ -254,6 +1149,25 @@ impl<'args, DB: sqlx::Database + DatabaseMetadata, M: Model<DB>> Default for Sel
             arguments: QueryBuilderArgs::default(),
             model: PhantomData,
             placeholder: DB::placeholder(),
+            deferred_joins: Vec::new(),
+            join_arguments: HashMap::new(),
+            cte_arguments: QueryBuilderArgs::default(),
+            having_arguments: QueryBuilderArgs::default(),
+            lock: None,
+            error: None,
+            soft_delete: soft_delete_column::<M>().map(SoftDeleteScope::Live),
         }
     }
 }
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M> std::fmt::Debug for SelectQueryBuilder<'args, DB, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::debug_query(f, "SelectQueryBuilder", self.to_sql(), &self.arguments_in_order())
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M> std::fmt::Display for SelectQueryBuilder<'args, DB, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::fmt_query(f, self.to_sql(), &self.arguments_in_order())
+    }
+}
@@ -0,0 +1,221 @@
+use crate::error::{Error, Result};
+use crate::model::{Model, TableMeta};
+use crate::query_builder::args::QueryBuilderArgs;
+use crate::query_builder::{DatabaseMetadata, IntoCondition, Placeholder, RowsAffected, util};
+use futures::future::BoxFuture;
+use sql::ToSql;
+use sql::query::Where;
+use sqlx::{Acquire, Executor, IntoArguments};
+use std::marker::PhantomData;
+
+/// Update every row of a model's table that matches a condition, e.g.
+///
+/// ```ignore
+/// let archived = User::update_where()
+///     .set("status", "archived")
+///     .set_raw("updated_at = now()")
+///     .where_("last_login < ?")
+///     .bind(cutoff)
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+pub struct UpdateQueryBuilder<'args, DB, M>
+where
+    DB: sqlx::Database,
+{
+    set: Vec<String>,
+    set_arguments: QueryBuilderArgs<'args, DB>,
+    where_: Where,
+    arguments: QueryBuilderArgs<'args, DB>,
+    placeholder: Placeholder,
+    /// An error from building the query, e.g. an unknown column, returned when the query is built.
+    error: Option<Error>,
+    model: PhantomData<M>,
+}
+
+impl<'args, DB, M> UpdateQueryBuilder<'args, DB, M>
+where
+    M: Sized + Send + Sync + Unpin + for<'r> sqlx::FromRow<'r, DB::Row> + 'static + Model<DB>,
+    DB: sqlx::Database + DatabaseMetadata,
+    DB::Arguments<'args>: IntoArguments<'args, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    /// Set a column to a value. Building the query fails if `column` is not a column of the model.
+    pub fn set<T>(mut self, column: &'static str, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        if !M::table_columns().contains(&column) {
+            self.error.get_or_insert(Error::OrmliteError(format!(
+                "`{column}` is not a column of `{}`",
+                M::table_name()
+            )));
+            return self;
+        }
+        self.set.push(format!("\"{column}\" = ?"));
+        self.set_arguments.add(value);
+        self
+    }
+
+    /// Add an assignment to the SET clause as is, e.g. "updated_at = now()" or "count = count + 1".
+    pub fn set_raw(mut self, assignment: &'static str) -> Self {
+        self.set.push(assignment.to_string());
+        self
+    }
+
+    /// Add a WHERE clause to the query. Like [`SelectQueryBuilder::where_`](super::SelectQueryBuilder::where_),
+    /// use `?` as the placeholder and add parameters with [`bind`](Self::bind), or pass a typed condition.
+    pub fn where_(mut self, clause: impl IntoCondition<'args, DB>) -> Self {
+        let (where_, args) = clause.into_condition().into_parts();
+        self.where_ = match self.where_ {
+            Where::And(mut v) => {
+                v.push(where_);
+                Where::And(v)
+            }
+            w => Where::And(vec![w, where_]),
+        };
+        self.arguments.extend(args);
+        self
+    }
+
+    /// Convenience method to add a `WHERE` and bind a value in one call.
+    pub fn where_bind<T>(self, clause: &'static str, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.where_(clause).bind(value)
+    }
+
+    /// Bind an argument to the WHERE clause.
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'args + Send + std::fmt::Debug + sqlx::Type<DB> + sqlx::Encode<'args, DB>,
+    {
+        self.arguments.add(value);
+        self
+    }
+
+    /// Run the update, and return the number of rows updated.
+    pub fn execute<'a, A>(self, db: A) -> BoxFuture<'a, Result<u64>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+        DB::QueryResult: RowsAffected,
+    {
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let mut conn = db.acquire().await?;
+            let result = util::query_with_recast_lifetime::<DB>(&text, args)
+                .execute(&mut *conn)
+                .await?;
+            Ok(result.rows_affected())
+        })
+    }
+
+    /// Run the update, and return the updated models.
+    pub fn fetch_all<'a, A>(self, db: A) -> BoxFuture<'a, Result<Vec<M>>>
+    where
+        'args: 'a,
+        A: 'a + Send + Acquire<'a, Database = DB>,
+    {
+        Box::pin(async move {
+            let (text, args) = self.into_query_and_args()?;
+            let text = format!("{text} RETURNING *");
+            let mut conn = db.acquire().await?;
+            let models = util::query_as_with_recast_lifetime::<DB, M>(&text, args)
+                .fetch_all(&mut *conn)
+                .await?;
+            Ok(models)
+        })
+    }
+
+    pub fn into_query_and_args(mut self) -> Result<(String, QueryBuilderArgs<'args, DB>)> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let q = self.text()?;
+        let mut args = self.set_arguments;
+        args.extend(self.arguments);
+        util::build_query_text(&q, args, &mut self.placeholder)
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M: TableMeta> UpdateQueryBuilder<'args, DB, M> {
+    /// The query as it's sent to the database, with the database's placeholders, e.g. to log it or to snapshot it in
+    /// tests. Unlike [`into_query_and_args`](Self::into_query_and_args), it keeps the builder.
+    pub fn to_sql(&self) -> Result<String> {
+        let q = self.text()?;
+        util::replace_and_check_placeholders(&q, &[&self.set_arguments, &self.arguments], &mut DB::placeholder())
+    }
+
+    /// The query, keeping its `?` placeholders.
+    fn text(&self) -> Result<String> {
+        if let Some(err) = &self.error {
+            return Err(Error::OrmliteError(err.to_string()));
+        }
+        if self.set.is_empty() {
+            return Err(Error::OrmliteError(
+                "Failing to build query. An update needs at least one column to set.".to_string(),
+            ));
+        }
+        let mut set = self.set.clone();
+        if let Some(column) = M::updated_at_column()
+            && !set.iter().any(|assignment| assigns(assignment, column))
+        {
+            set.push(format!("\"{column}\" = CURRENT_TIMESTAMP"));
+        }
+        // Bump the version, so models read before the update can't overwrite it.
+        if let Some(column) = M::version_column()
+            && !set.iter().any(|assignment| assigns(assignment, column))
+        {
+            set.push(format!("\"{column}\" = \"{column}\" + 1"));
+        }
+        let mut q = format!("UPDATE \"{}\" SET {}", M::table_name(), set.join(", "));
+        if !self.where_.is_empty() {
+            q.push_str(" WHERE ");
+            q.push_str(&self.where_.to_sql(DB::dialect()));
+        }
+        Ok(q)
+    }
+}
+
+/// Whether an assignment of the SET clause, e.g. `"name" = ?` or `count = count + 1`, assigns `column`.
+fn assigns(assignment: &str, column: &str) -> bool {
+    let assignment = assignment.trim_start().trim_start_matches('"');
+    assignment
+        .strip_prefix(column)
+        .is_some_and(|rest| rest.trim_start_matches('"').trim_start().starts_with('='))
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M> Default for UpdateQueryBuilder<'args, DB, M> {
+    fn default() -> Self {
+        Self {
+            set: Vec::new(),
+            set_arguments: QueryBuilderArgs::default(),
+            where_: Where::And(Vec::new()),
+            arguments: QueryBuilderArgs::default(),
+            placeholder: DB::placeholder(),
+            error: None,
+            model: PhantomData,
+        }
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M: TableMeta> std::fmt::Debug for UpdateQueryBuilder<'args, DB, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::debug_query(
+            f,
+            "UpdateQueryBuilder",
+            self.to_sql(),
+            &[&self.set_arguments, &self.arguments],
+        )
+    }
+}
+
+impl<'args, DB: sqlx::Database + DatabaseMetadata, M: TableMeta> std::fmt::Display
+    for UpdateQueryBuilder<'args, DB, M>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        util::fmt_query(f, self.to_sql(), &[&self.set_arguments, &self.arguments])
+    }
+}
@@ -1,14 +1,43 @@
-use crate::query_builder::args::QueryBuilderArgs;
+use crate::query_builder::args::{QueryBuilderArgs, Width};
+use crate::query_builder::lock::RowLock;
+use crate::query_builder::{DatabaseMetadata, Placeholder};
 use crate::{Error, Result};
+use futures::stream::{BoxStream, Stream};
+use sql::{Select, ToSql};
 use sqlparser::dialect::GenericDialect;
 use sqlparser::tokenizer::{Token, Tokenizer};
-use sqlx::query::QueryAs;
+use sqlx::query::{Query, QueryAs};
 
-pub fn replace_placeholders<T: Iterator<Item = String>>(
+/// Replace `?` placeholders with the database's placeholders. The n-th `?` expands to a placeholder for each value
+/// bound to it by `widths[n]` (one if `widths` is shorter). This is how a list bound to a single `?` is expanded. An
+/// empty list expands to a subquery without rows, which `NULL` can't stand in for: `x NOT IN (NULL)` matches nothing.
+/// The subquery selects the list's type, because Postgres can't compare a column to an untyped `NULL`.
+pub(crate) fn replace_placeholders<T: Iterator<Item = String>>(
     sql: &str,
     placeholder_generator: &mut T,
+    widths: &[Width],
 ) -> Result<(String, usize)> {
     let mut placeholder_count = 0usize;
+    let mut question_marks = 0usize;
+    let mut push_placeholder = |buf: &mut String, placeholder_count: &mut usize| {
+        let width = match widths.get(question_marks) {
+            None | Some(Width::One) => 1,
+            Some(Width::List { len: 0, sql_type }) => {
+                question_marks += 1;
+                buf.push_str(&format!("SELECT CAST(NULL AS {sql_type}) WHERE 1 = 0"));
+                return;
+            }
+            Some(Width::List { len, .. }) => *len,
+        };
+        question_marks += 1;
+        for i in 0..width {
+            if i > 0 {
+                buf.push_str(", ");
+            }
+            buf.push_str(&placeholder_generator.next().unwrap());
+        }
+        *placeholder_count += width;
+    };
     let dialect = GenericDialect {};
     // note this lib is inefficient because it's copying strings everywhere, instead
     // of using slices and an appropriate lifetime. probably want to swap out the lib at some point
@@ -18,16 +47,10 @@ pub fn replace_placeholders<T: Iterator<Item = String>>(
     let mut it = tokens.iter();
     while let Some(tok) = it.next() {
         match tok {
-            Token::Placeholder(_) => {
-                buf.push_str(&placeholder_generator.next().unwrap());
-                placeholder_count += 1;
-            }
+            Token::Placeholder(_) => push_placeholder(&mut buf, &mut placeholder_count),
             Token::Char(c) => {
                 match c {
-                    '?' => {
-                        buf.push_str(&placeholder_generator.next().unwrap());
-                        placeholder_count += 1;
-                    }
+                    '?' => push_placeholder(&mut buf, &mut placeholder_count),
                     '$' => {
                         let next_tok = it.next();
                         if let Some(next_tok) = next_tok {
@@ -52,6 +75,96 @@ pub fn replace_placeholders<T: Iterator<Item = String>>(
     Ok((buf, placeholder_count))
 }
 
+/// Render the query for the database, with its row lock, and check that there's an argument for every placeholder.
+pub(super) fn build_query<'args, DB>(
+    query: &Select,
+    lock: Option<RowLock>,
+    args: QueryBuilderArgs<'args, DB>,
+    placeholder: &mut Placeholder,
+) -> Result<(String, QueryBuilderArgs<'args, DB>)>
+where
+    DB: sqlx::Database + DatabaseMetadata,
+{
+    build_query_text(&select_text::<DB>(query, lock)?, args, placeholder)
+}
+
+/// Render the query with its row lock, keeping its `?` placeholders.
+pub(super) fn select_text<DB: DatabaseMetadata>(query: &Select, lock: Option<RowLock>) -> Result<String> {
+    let mut q = query.to_sql(DB::dialect());
+    if let Some(lock) = lock {
+        q.push_str(&lock.to_sql(DB::dialect())?);
+    }
+    Ok(q)
+}
+
+/// Like [`build_query`], for a query that's already rendered.
+pub(super) fn build_query_text<'args, DB>(
+    q: &str,
+    args: QueryBuilderArgs<'args, DB>,
+    placeholder: &mut Placeholder,
+) -> Result<(String, QueryBuilderArgs<'args, DB>)>
+where
+    DB: sqlx::Database,
+{
+    let q = replace_and_check_placeholders(q, &[&args], placeholder)?;
+    Ok((q, args))
+}
+
+/// Replace the `?` placeholders of `q`, and check that there's an argument for every placeholder. `args` are the
+/// parts of the query's arguments, in placeholder order.
+pub(super) fn replace_and_check_placeholders<DB: sqlx::Database>(
+    q: &str,
+    args: &[&QueryBuilderArgs<'_, DB>],
+    placeholder: &mut Placeholder,
+) -> Result<String> {
+    let widths = args
+        .iter()
+        .flat_map(|a| a.placeholder_widths())
+        .cloned()
+        .collect::<Vec<_>>();
+    let len = args.iter().map(|a| a.len()).sum::<usize>();
+    let (q, placeholder_count) = replace_placeholders(q, placeholder, &widths)?;
+    if placeholder_count != len {
+        return Err(Error::OrmliteError(format!(
+            "Failing to build query. {} placeholders were found in the query, but \
+            {} arguments were provided.",
+            placeholder_count, len,
+        )));
+    }
+    Ok(q)
+}
+
+/// Write the query, then its bound values as a comment, for the `Display` form of the query builders.
+pub(super) fn fmt_query<DB: sqlx::Database>(
+    f: &mut std::fmt::Formatter<'_>,
+    sql: Result<String>,
+    args: &[&QueryBuilderArgs<'_, DB>],
+) -> std::fmt::Result {
+    match sql {
+        Ok(sql) => f.write_str(&sql)?,
+        Err(err) => write!(f, "-- {err}")?,
+    }
+    let values = args.iter().flat_map(|a| a.values()).collect::<Vec<_>>();
+    if !values.is_empty() {
+        write!(f, "\n-- {values:?}")?;
+    }
+    Ok(())
+}
+
+/// Write the query and its bound values, for the `Debug` form of the query builders.
+pub(super) fn debug_query<DB: sqlx::Database>(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    sql: Result<String>,
+    args: &[&QueryBuilderArgs<'_, DB>],
+) -> std::fmt::Result {
+    let values = args.iter().flat_map(|a| a.values()).collect::<Vec<_>>();
+    f.debug_struct(name)
+        .field("sql", &sql)
+        .field("arguments", &values)
+        .finish()
+}
+
 pub(super) fn query_as_with_recast_lifetime<'q, 'r, DB, Model>(
     s: &'q str,
     args: QueryBuilderArgs<'r, DB>,
@@ -68,6 +181,50 @@ where
     sqlx::query_as_with(s, recast_args)
 }
 
+pub(super) fn query_with_recast_lifetime<'q, 'r, DB>(
+    s: &'q str,
+    args: QueryBuilderArgs<'r, DB>,
+) -> Query<'q, DB, QueryBuilderArgs<'q, DB>>
+where
+    'r: 'q,
+    DB: sqlx::Database,
+{
+    // See query_as_with_recast_lifetime for why this is safe.
+    let recast_args = unsafe { std::mem::transmute::<QueryBuilderArgs<'r, DB>, QueryBuilderArgs<'q, DB>>(args) };
+    sqlx::query_with(s, recast_args)
+}
+
+/// A stream of query results that owns the query text it was created from.
+pub(super) struct OwnedQueryStream<'a, T> {
+    // Declared before `_text`, so it's dropped before the text it borrows.
+    stream: BoxStream<'a, Result<T>>,
+    _text: String,
+}
+
+impl<'a, T> OwnedQueryStream<'a, T> {
+    /// `fetch` is given the query text, borrowed for as long as the stream lives.
+    pub(super) fn new(text: String, fetch: impl FnOnce(&'a str) -> BoxStream<'a, Result<T>>) -> Self {
+        // unsafe is safe b/c the text is owned by the stream and dropped after it. The String's heap buffer doesn't
+        // move when the String does, and it's never mutated.
+        let borrowed = unsafe { &*(text.as_str() as *const str) };
+        Self {
+            stream: fetch(borrowed),
+            _text: text,
+        }
+    }
+}
+
+impl<T> Stream for OwnedQueryStream<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +237,7 @@ mod tests {
         let (sql, placeholder_count) = replace_placeholders(
             "SELECT * FROM users WHERE id = ? OR id = ? OR id = ?",
             &mut placeholder_generator,
+            &[],
         )?;
         assert_eq!(sql, "SELECT * FROM users WHERE id = $1 OR id = $2 OR id = $3");
         assert_eq!(placeholder_count, 3);
@@ -90,9 +248,36 @@ mod tests {
     fn test_leave_placeholders_alone() -> Result<()> {
         let mut placeholder_generator = vec!["$1", "$2", "$3"].into_iter().map(|s| s.to_string());
         let (sql, placeholder_count) =
-            replace_placeholders("SELECT * FROM users WHERE email = $1", &mut placeholder_generator)?;
+            replace_placeholders("SELECT * FROM users WHERE email = $1", &mut placeholder_generator, &[])?;
         assert_eq!(sql, "SELECT * FROM users WHERE email = $1");
         assert_eq!(placeholder_count, 1);
         Ok(())
     }
+
+    #[test]
+    fn test_expand_list_placeholders() -> Result<()> {
+        let mut placeholder_generator = (1..).map(|i| format!("${i}"));
+        let (sql, placeholder_count) = replace_placeholders(
+            "SELECT * FROM users WHERE id IN (?) AND name = ? AND org_id IN (?)",
+            &mut placeholder_generator,
+            &[
+                Width::List {
+                    len: 3,
+                    sql_type: "INT4".to_string(),
+                },
+                Width::One,
+                Width::List {
+                    len: 0,
+                    sql_type: "INT4".to_string(),
+                },
+            ],
+        )?;
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE id IN ($1, $2, $3) AND name = $4 \
+             AND org_id IN (SELECT CAST(NULL AS INT4) WHERE 1 = 0)"
+        );
+        assert_eq!(placeholder_count, 4);
+        Ok(())
+    }
 }
@@ -3,7 +3,7 @@ use anyhow::Result as AnyResult;
 use ormlite_attr::ModelMeta;
 use ormlite_attr::Type;
 use ormlite_attr::{schema_from_filepaths, ColumnMeta, Ident, InnerType};
-use sql::{schema::Column, Constraint, Schema, Table};
+use sql::{migrate::Statement, schema::Column, Constraint, Dialect, Schema, Table, ToSql};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -13,9 +13,10 @@ pub fn schema_from_ormlite_project(paths: &[&Path], c: &Config) -> AnyResult<Sch
     let primary_key_type: HashMap<String, InnerType> = fs_schema
         .tables
         .iter()
-        .map(|t| {
-            let pkey_ty = t.pkey.ty.inner_type().clone();
-            (t.ident.to_string(), pkey_ty)
+        // only models with a single column primary key can be joined
+        .filter_map(|t| match t.pkeys.as_slice() {
+            [pkey] => Some((t.ident.to_string(), pkey.ty.inner_type().clone())),
+            _ => None,
         })
         .collect();
     for t in &mut fs_schema.tables {
@@ -25,24 +26,33 @@ pub fn schema_from_ormlite_project(paths: &[&Path], c: &Config) -> AnyResult<Sch
             if let Some(f) = fs_schema.type_reprs.get(&inner.ident.to_string()) {
                 inner.ident = Ident::from(f);
             }
-            // replace join types with the primary key type.
-            if c.ty.is_join() {
+            // replace join types with the primary key type. Other joins don't have a column on this table.
+            if c.is_join_one() {
                 let model_name = c.ty.inner_type_name();
                 let pkey = primary_key_type
                     .get(&model_name)
                     .expect(&format!("Could not find model {} for join", model_name));
-                c.ty = Type::Inner(pkey.clone());
+                c.ty = if c.ty.is_option() {
+                    Type::Option(Box::new(Type::Inner(pkey.clone())))
+                } else {
+                    Type::Inner(pkey.clone())
+                };
             }
         }
     }
-    for table in fs_schema.tables {
-        let table = Table::from_meta(&table);
+    for table in &fs_schema.tables {
+        let table = Table::from_meta(table);
         schema.tables.push(table);
     }
+    let join_tables = join_tables(&fs_schema.tables, &schema.tables);
+    // tables with a composite primary key can't be referenced by a single column
     let mut table_names: HashMap<String, (String, String)> = schema
         .tables
         .iter()
-        .map(|t| (t.name.clone(), (t.name.clone(), t.primary_key().unwrap().name.clone())))
+        .filter_map(|t| match primary_key_columns(t).as_slice() {
+            [pkey] => Some((t.name.clone(), (t.name.clone(), pkey.to_string()))),
+            _ => None,
+        })
         .collect();
     for (alias, real) in &c.table.aliases {
         let Some(real) = table_names.get(real) else {
@@ -50,9 +60,13 @@ pub fn schema_from_ormlite_project(paths: &[&Path], c: &Config) -> AnyResult<Sch
         };
         table_names.insert(alias.clone(), real.clone());
     }
+    // join tables have a composite primary key, so add them after collecting the tables they can reference.
+    schema.tables.extend(join_tables);
     for table in &mut schema.tables {
+        // the columns of a composite primary key usually reference other tables
+        let composite = primary_key_columns(table).len() > 1;
         for column in &mut table.columns {
-            if column.primary_key {
+            if column.primary_key && !composite {
                 continue;
             }
             if column.name.ends_with("_id") || column.name.ends_with("_uuid") {
@@ -69,6 +83,81 @@ pub fn schema_from_ormlite_project(paths: &[&Path], c: &Config) -> AnyResult<Sch
     Ok(schema)
 }
 
+pub fn primary_key_columns(table: &Table) -> Vec<&str> {
+    table.columns.iter().filter(|c| c.primary_key).map(|c| c.name.as_str()).collect()
+}
+
+/// Render a migration statement. Columns render their own `PRIMARY KEY`, which only works for a single column, so a
+/// table with a composite primary key gets a `PRIMARY KEY (...)` table constraint instead.
+pub fn statement_to_sql(statement: &Statement, dialect: Dialect) -> String {
+    let Statement::CreateTable(table) = statement else {
+        return statement.to_sql(dialect);
+    };
+    let pkeys = primary_key_columns(table);
+    if pkeys.len() < 2 {
+        return statement.to_sql(dialect);
+    }
+    let name = match &table.schema {
+        Some(schema) => format!("\"{schema}\".\"{}\"", table.name),
+        None => format!("\"{}\"", table.name),
+    };
+    let columns = table.columns.iter().map(|c| {
+        Column {
+            primary_key: false,
+            ..c.clone()
+        }
+        .to_sql(dialect)
+    });
+    let constraint = format!("PRIMARY KEY (\"{}\")", pkeys.join("\", \""));
+    let body = columns.chain([constraint]).collect::<Vec<_>>().join(",\n");
+    format!("CREATE TABLE IF NOT EXISTS {name} (\n{body}\n)")
+}
+
+/// Join tables for many to many joins. They don't have a model, so create one for each `join_table`
+/// that isn't already a table, with a column referencing each side of the join. The two columns are the primary key,
+/// so two models are only related once.
+pub fn join_tables(models: &[ModelMeta], tables: &[Table]) -> Vec<Table> {
+    let mut join_tables: Vec<Table> = Vec::new();
+    let pkey_type = |name: &str| {
+        let table = tables.iter().find(|t| t.name == name)?;
+        Some(table.primary_key()?.typ.clone())
+    };
+    for model in models {
+        for c in &model.columns {
+            let Some(joined_name) = c.joined_struct_name() else {
+                continue;
+            };
+            let Some(joined) = models.iter().find(|m| m.ident == joined_name) else {
+                continue;
+            };
+            let Some((name, local_key, foreign_key)) = c.join_table_columns(model, joined) else {
+                continue;
+            };
+            if tables.iter().chain(&join_tables).any(|t| t.name == name) {
+                continue;
+            }
+            let (Some(local_type), Some(foreign_type)) = (pkey_type(&model.name), pkey_type(&joined.name)) else {
+                continue;
+            };
+            let column = |name: String, typ: sql::Type| Column {
+                name,
+                typ,
+                default: None,
+                nullable: false,
+                primary_key: true,
+                constraint: None,
+                generated: None,
+            };
+            join_tables.push(Table {
+                schema: None,
+                name: name.to_string(),
+                columns: vec![column(local_key, local_type), column(foreign_key, foreign_type)],
+            });
+        }
+    }
+    join_tables
+}
+
 #[derive(Debug)]
 pub struct Options {
     pub verbose: bool,
@@ -90,7 +179,7 @@ impl FromMeta for Table {
                     return None;
                 }
                 let mut col = Option::<Column>::from_meta(c)?;
-                col.primary_key = model.pkey.name == col.name;
+                col.primary_key = model.is_pkey(c);
                 Some(col)
             })
             .collect();
@@ -109,10 +198,22 @@ impl FromMeta for Option<Column> {
         if meta.json {
             ty.ty = sql::Type::Jsonb;
         }
+        if let (sql::Type::Text, Some(max)) = (&ty.ty, meta.validation.max_length) {
+            ty.ty = sql::Type::Other(format!("character varying({max})"));
+        }
+        // Inserts set the timestamps, but a default keeps the column filled for rows inserted without ormlite.
+        // Versions start at 0.
+        let default = if meta.is_timestamp() {
+            Some(sql::query::Expr::Raw("CURRENT_TIMESTAMP".to_string()))
+        } else if meta.version {
+            Some(sql::query::Expr::Raw("0".to_string()))
+        } else {
+            None
+        };
         Some(Column {
             name: meta.name.clone(),
             typ: ty.ty,
-            default: None,
+            default,
             nullable: ty.nullable,
             primary_key: meta.marked_primary_key,
             constraint: None,
@@ -238,4 +339,53 @@ mod tests {
         };
         assert_eq!(*inner, SqlType::Uuid);
     }
+
+    #[test]
+    fn test_column_defaults() {
+        let mut meta = ColumnMeta::mock("created_at", "DateTime");
+        assert!(Option::<Column>::from_meta(&meta).unwrap().default.is_none());
+        meta.created_at = true;
+        let column = Option::<Column>::from_meta(&meta).unwrap();
+        assert_eq!(
+            column.to_sql(Dialect::Postgres),
+            r#""created_at" timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP"#
+        );
+        let mut meta = ColumnMeta::mock("version", "i32");
+        meta.version = true;
+        let column = Option::<Column>::from_meta(&meta).unwrap();
+        assert_eq!(column.to_sql(Dialect::Postgres), r#""version" integer NOT NULL DEFAULT 0"#);
+    }
+
+    #[test]
+    fn test_max_length() {
+        let mut meta = ColumnMeta::mock("email", "String");
+        meta.validation.max_length = Some(255);
+        let column = Option::<Column>::from_meta(&meta).unwrap();
+        assert_eq!(column.to_sql(Dialect::Postgres), r#""email" character varying(255) NOT NULL"#);
+    }
+
+    #[test]
+    fn test_composite_primary_key() {
+        let column = |name: &str| Column {
+            name: name.to_string(),
+            typ: sql::Type::I32,
+            default: None,
+            nullable: false,
+            primary_key: true,
+            constraint: None,
+            generated: None,
+        };
+        let table = Table {
+            schema: None,
+            name: "user_role".to_string(),
+            columns: vec![column("user_id"), column("role_id")],
+        };
+        assert_eq!(
+            statement_to_sql(&Statement::CreateTable(table), Dialect::Postgres),
+            "CREATE TABLE IF NOT EXISTS \"user_role\" (\n\
+             \"user_id\" integer NOT NULL,\n\
+             \"role_id\" integer NOT NULL,\n\
+             PRIMARY KEY (\"user_id\", \"role_id\")\n)"
+        );
+    }
 }
@@ -0,0 +1,88 @@
+//! Checks of `#[ormlite(validate(...))]` fields. The generated code calls these before writing a model.
+use crate::{Error, Result};
+pub use regex::Regex;
+
+/// A value with a length, for `validate(length(...))`.
+pub trait HasLength {
+    /// The length, or `None` for a missing optional value, which is always valid.
+    fn length(&self) -> Option<usize>;
+}
+
+impl HasLength for String {
+    fn length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl<T> HasLength for Vec<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: HasLength> HasLength for Option<T> {
+    fn length(&self) -> Option<usize> {
+        self.as_ref().and_then(T::length)
+    }
+}
+
+/// A text value, for `validate(regex = "...")`.
+pub trait AsText {
+    /// The text, or `None` for a missing optional value, which is always valid.
+    fn as_text(&self) -> Option<&str>;
+}
+
+impl AsText for String {
+    fn as_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: AsText> AsText for Option<T> {
+    fn as_text(&self) -> Option<&str> {
+        self.as_ref().and_then(T::as_text)
+    }
+}
+
+pub fn length(field: &'static str, value: &impl HasLength, min: Option<usize>, max: Option<usize>) -> Result<()> {
+    let Some(length) = value.length() else {
+        return Ok(());
+    };
+    let message = match (min, max) {
+        (Some(min), _) if length < min => format!("must have a length of at least {min}, but has {length}"),
+        (_, Some(max)) if length > max => format!("must have a length of at most {max}, but has {length}"),
+        _ => return Ok(()),
+    };
+    Err(Error::Validation { field, message })
+}
+
+pub fn regex(field: &'static str, value: &impl AsText, regex: &Regex) -> Result<()> {
+    match value.as_text() {
+        Some(text) if !regex.is_match(text) => Err(Error::Validation {
+            field,
+            message: format!("must match the pattern `{}`", regex.as_str()),
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length() {
+        assert!(length("name", &"ab".to_string(), Some(2), Some(3)).is_ok());
+        assert!(length("name", &None::<String>, Some(2), None).is_ok());
+        let err = length("name", &"abcd".to_string(), None, Some(3)).unwrap_err();
+        assert_eq!(err.to_string(), "name must have a length of at most 3, but has 4");
+        assert!(length("tags", &vec![1], Some(2), None).is_err());
+    }
+
+    #[test]
+    fn test_regex() {
+        let re = Regex::new("^[a-z]+$").unwrap();
+        assert!(regex("slug", &"abc".to_string(), &re).is_ok());
+        assert!(regex("slug", &Some("ABC".to_string()), &re).is_err());
+    }
+}
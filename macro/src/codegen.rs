@@ -1,9 +1,11 @@
+pub mod columns;
 pub mod common;
 pub mod from_row;
 pub mod insert;
 pub mod insert_model;
 pub mod into_arguments;
 pub mod join_description;
+pub mod load_join;
 pub mod meta;
 pub mod model;
 pub mod model_builder;
@@ -0,0 +1,54 @@
+use ormlite_attr::{ColumnMeta, ModelMeta, Type};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+/// The type a column's handle compares against. Nullable columns compare against the inner type (use `is_null` for
+/// NULL), and many to one joins against the id of the joined model.
+fn column_value_type(c: &ColumnMeta) -> TokenStream {
+    if let Some(joined) = c.ty.joined_type() {
+        return quote! { <#joined as ::ormlite::model::JoinMeta>::IdType };
+    }
+    match &c.ty {
+        Type::Option(inner) => quote! { #inner },
+        ty => quote! { #ty },
+    }
+}
+
+/// Typed handles to the model's columns, returned by `<Model>::columns()`. Json columns don't get one, because
+/// their values are bound wrapped in `Json`.
+pub fn struct_Columns(ast: &DeriveInput, attr: &ModelMeta) -> TokenStream {
+    let model = &attr.ident;
+    let columns_struct = attr.columns_struct();
+    let vis = &ast.vis;
+    let table = &attr.name;
+    let columns = attr.database_columns().filter(|c| !c.is_json()).collect::<Vec<_>>();
+
+    let fields = columns.iter().map(|c| {
+        let name = &c.ident;
+        let ty = column_value_type(c);
+        quote! { pub #name: ::ormlite::query_builder::TypedColumn<#model, #ty> }
+    });
+
+    let values = columns.iter().map(|c| {
+        let name = &c.ident;
+        let column = &c.name;
+        quote! { #name: ::ormlite::query_builder::TypedColumn::new(#table, #column) }
+    });
+
+    quote! {
+        #vis struct #columns_struct {
+            #(#fields,)*
+        }
+
+        impl #model {
+            /// Typed handles to the columns, for filters that are checked at compile time, e.g.
+            /// `.where_(Self::columns().id.eq(1))`.
+            pub fn columns() -> #columns_struct {
+                #columns_struct {
+                    #(#values,)*
+                }
+            }
+        }
+    }
+}
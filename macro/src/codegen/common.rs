@@ -75,6 +75,7 @@ fn recursive_primitive_types<'a>(table: &'a ModelMeta, cache: &'a MetadataCache)
     table
         .columns
         .iter()
+        .filter(|c| !c.is_join_many())
         .map(|c| recursive_primitive_types_ty(&c.ty, cache))
         .flatten()
         .collect()
@@ -85,6 +86,7 @@ pub(crate) fn table_primitive_types<'a>(attr: &'a TableMeta, cache: &'a Metadata
         .iter()
         .filter(|c| !c.skip)
         .filter(|c| !c.json)
+        .filter(|c| !c.is_join_many())
         .map(|c| recursive_primitive_types_ty(&c.ty, cache))
         .flatten()
         .unique()
@@ -139,6 +141,90 @@ pub fn insertion_binding(c: &ColumnMeta) -> TokenStream {
     }
 }
 
+/// The condition matching a model by primary key, e.g. `id = ?`, or `user_id = ? AND role_id = ?` for a composite
+/// primary key.
+pub fn pkey_condition(attr: &ModelMeta, placeholder: &mut Placeholder) -> String {
+    attr.pkeys
+        .iter()
+        .map(|c| format!("{} = {}", c.name, placeholder.next().unwrap()))
+        .join(" AND ")
+}
+
+/// Call the `ModelHooks` method `hook` with `target` if the model is marked `#[ormlite(hooks)]`, and nothing otherwise.
+/// Assumed bindings:
+/// - `conn`: a connection, or anything that dereferences to one
+pub fn call_hook(db: &TokenStream, attr: &TableMeta, hook: &str, target: TokenStream) -> TokenStream {
+    if !attr.hooks {
+        return TokenStream::new();
+    }
+    let model = &attr.ident;
+    let hook = Ident::from(hook);
+    quote! {
+        <#model as ::ormlite::model::ModelHooks<#db>>::#hook(#target, &mut *conn).await?;
+    }
+}
+
+/// Open `conn`, the connection a model's query runs on, from `db`, an `Acquire`. With `#[ormlite(hooks)]`, it's a
+/// transaction, so if a hook, validation or the query fails, what the hooks wrote is rolled back. See `commit_conn`.
+pub fn acquire_conn(attr: &TableMeta, db: TokenStream) -> TokenStream {
+    if attr.hooks {
+        quote! { let mut conn = #db.begin().await?; }
+    } else {
+        quote! { let mut conn = #db.acquire().await?; }
+    }
+}
+
+/// Commit the transaction `acquire_conn` opened for a model with hooks, and nothing otherwise.
+pub fn commit_conn(attr: &TableMeta) -> TokenStream {
+    if !attr.hooks {
+        return TokenStream::new();
+    }
+    quote! { conn.commit().await?; }
+}
+
+/// Check the `#[ormlite(validate(...))]` columns of `target`, returning `Error::Validation` from the enclosing
+/// function if one is invalid. `target` can be the model, or a builder, whose unset fields are valid.
+pub fn validate_columns<'a>(columns: impl Iterator<Item = &'a ColumnMeta>, target: TokenStream) -> TokenStream {
+    let checks = columns.filter(|c| !c.is_join()).map(|c| {
+        let ident = &c.ident;
+        let field = ident.to_string();
+        let validation = &c.validation;
+        let length = (validation.min_length.is_some() || validation.max_length.is_some()).then(|| {
+            let min = quote_option(validation.min_length);
+            let max = quote_option(validation.max_length);
+            quote! {
+                ::ormlite::__private::validate::length(#field, &#target.#ident, #min, #max)?;
+            }
+        });
+        let regex = validation.regex.as_ref().map(|pattern| {
+            if let Err(e) = ormlite_core::validate::Regex::new(pattern) {
+                panic!("Column {ident} has an invalid regex: {e}");
+            }
+            quote! {
+                {
+                    static REGEX: ::std::sync::LazyLock<::ormlite::__private::validate::Regex> =
+                        ::std::sync::LazyLock::new(|| ::ormlite::__private::validate::Regex::new(#pattern).unwrap());
+                    ::ormlite::__private::validate::regex(#field, &#target.#ident, &REGEX)?;
+                }
+            }
+        });
+        quote! {
+            #length
+            #regex
+        }
+    });
+    quote! {
+        #(#checks)*
+    }
+}
+
+fn quote_option(value: Option<usize>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
 pub trait OrmliteCodegen {
     fn dialect_ts(&self) -> TokenStream;
     fn database_ts(&self) -> TokenStream;
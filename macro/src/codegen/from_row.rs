@@ -10,59 +10,8 @@ pub fn impl_FromRow(db: &dyn OrmliteCodegen, attr: &TableMeta, cache: &MetadataC
     let bounds = from_row_bounds(db, attr, cache);
     let row = db.row();
 
-    let prefix_branches = attr.columns.iter().filter(|&c| c.is_join_one()).map(|c| {
-        let name = &c.ident.to_string();
-        let iden = &c.ident;
-        let meta = cache
-            .get(c.joined_struct_name().unwrap().as_str())
-            .expect("Joined struct not found");
-        let prefixed_columns = meta.database_columns().map(|c| format!("__{}__{}", iden, c.ident));
-        let path = c.joined_model();
-        let result = quote! {
-            #path::from_row_using_aliases(row, &[
-                #(
-                    #prefixed_columns,
-                )*
-            ])?
-        };
-        quote! {
-            #name => {
-                model.#iden = ::ormlite::model::Join::_query_result(#result);
-            }
-        }
-    });
-
     let field_names = attr.database_columns().map(|c| &c.name);
-
-    let map_join = if attr.columns.iter().any(|c| c.is_join()) {
-        quote! {
-            let mut prefixes = ::ormlite::Row::columns(row).iter().filter_map(|c| {
-                let name = ::ormlite::Column::name(c);
-                if name.starts_with("__") {
-                    name.rsplitn(2, "__").last().map(|s| &s[2..])
-                } else {
-                    None
-                }
-            })
-                .collect::<Vec<_>>();
-            prefixes.sort();
-            prefixes.dedup();
-            for prefix in prefixes {
-                match prefix {
-                    #(
-                        #prefix_branches
-                    )*
-                    _ => {
-                        return Err(::ormlite::SqlxError::Decode(
-                            Box::new(::ormlite::Error::OrmliteError(format!("Unknown column prefix: {}", prefix))),
-                        ));
-                    }
-                }
-            }
-        }
-    } else {
-        TokenStream::new()
-    };
+    let load_joins = load_joins_with_prefix(attr, quote! { "__" }, cache);
     let model = &attr.ident;
     quote! {
         impl<'a> ::ormlite::model::FromRow<'a, #row> for #model
@@ -78,7 +27,7 @@ pub fn impl_FromRow(db: &dyn OrmliteCodegen, attr: &TableMeta, cache: &MetadataC
                         #field_names,
                     )*
                 ])?;
-                #map_join
+                #load_joins
                 Ok(model)
             }
         }
@@ -98,7 +47,7 @@ pub fn impl_from_row_using_aliases(
         .columns
         .iter()
         .map(|c| {
-            if c.skip {
+            if c.skip || c.is_join_many() {
                 let id = &c.ident;
                 return quote! {
                     let #id = Default::default();
@@ -110,10 +59,13 @@ pub fn impl_from_row_using_aliases(
         })
         .collect::<Vec<_>>();
 
+    let field_names = attr.database_columns().map(|c| &c.name);
+    let load_joins = load_joins_with_prefix(attr, quote! { prefix }, metadata_cache);
+    let bounds = bounds.collect::<Vec<_>>();
     let model = &attr.ident;
     quote! {
         impl #model {
-            pub fn from_row_using_aliases<'a>(row: &'a #row, aliases: &'a [&str]) -> ::std::result::Result<Self, ::ormlite::SqlxError>
+            pub fn from_row_using_aliases<'a>(row: &'a #row, aliases: &[&str]) -> ::std::result::Result<Self, ::ormlite::SqlxError>
                 where
                     #(
                         #bounds
@@ -124,7 +76,69 @@ pub fn impl_from_row_using_aliases(
                 )*
                 Ok(Self { #(#fields,)* })
             }
+
+            /// Decode the model from columns aliased as `<prefix><column>`, along with any joins of the
+            /// model that were selected, aliased as `<prefix><field>__<column>`.
+            #[doc(hidden)]
+            pub fn _from_row_with_prefix<'a>(row: &'a #row, prefix: &str) -> ::std::result::Result<Self, ::ormlite::SqlxError>
+                where
+                    #(
+                        #bounds
+                    )*
+            {
+                let aliases = [
+                    #(
+                        format!("{}{}", prefix, #field_names),
+                    )*
+                ];
+                let aliases = aliases.iter().map(String::as_str).collect::<Vec<_>>();
+                #[allow(unused_mut)]
+                let mut model = Self::from_row_using_aliases(row, &aliases)?;
+                #load_joins
+                Ok(model)
+            }
+        }
+    }
+}
+
+/// Hydrate the many to one joins that were selected with the query. Join columns are aliased as
+/// `<prefix><field>__<column>`, so a join of a join has a longer prefix, e.g. `__organization__owner__id`.
+/// A join is left unloaded if the LEFT JOIN didn't match a row, i.e. its primary key is NULL.
+/// Assumed bindings:
+/// - `row`: the row to decode
+/// - `model`: the model, with its own columns already decoded
+fn load_joins_with_prefix(attr: &TableMeta, prefix: TokenStream, cache: &MetadataCache) -> TokenStream {
+    let joins = attr.many_to_one_joins().map(|c| {
+        let field = c.ident.to_string();
+        let id = &c.ident;
+        let path = c.joined_model();
+        let struct_name = c.joined_struct_name().unwrap();
+        let joined_pkey = &cache
+            .get(&struct_name)
+            .unwrap_or_else(|| panic!("Did not find metadata for joined struct: {}", struct_name))
+            .pkey()
+            .name;
+        let join = quote! {
+            ::ormlite::model::Join::_query_result(#path::_from_row_with_prefix(row, &join_prefix)?)
+        };
+        let join = if c.is_option() {
+            quote! { Some(#join) }
+        } else {
+            join
+        };
+        quote! {
+            let join_prefix = format!("{}{}__", #prefix, #field);
+            if ::ormlite::Row::columns(row).iter().any(|c| ::ormlite::Column::name(c).starts_with(&join_prefix)) {
+                let joined_id: Option<<#path as ::ormlite::model::JoinMeta>::IdType> =
+                    ::ormlite::Row::try_get(row, format!("{}{}", join_prefix, #joined_pkey).as_str())?;
+                if joined_id.is_some() {
+                    model.#id = #join;
+                }
+            }
         }
+    });
+    quote! {
+        #(#joins)*
     }
 }
 
@@ -134,9 +148,14 @@ pub fn from_row_for_column(get_value: TokenStream, col: &ColumnMeta) -> TokenStr
     let ty = &col.ty;
     if col.is_join() {
         let id_id = Ident::from(format!("{}_id", id));
+        let join = if col.is_option() {
+            quote! { #id_id.map(::ormlite::model::Join::new_with_id) }
+        } else {
+            quote! { ::ormlite::model::Join::new_with_id(#id_id) }
+        };
         quote! {
             let #id_id: <#ty as ::ormlite::model::JoinMeta>::IdType = ::ormlite::Row::try_get(row, #get_value)?;
-            let #id = ::ormlite::model::Join::new_with_id(#id_id);
+            let #id = #join;
         }
     } else if col.json {
         if let Type::Option(inner) = ty {
@@ -1,5 +1,7 @@
 use itertools::Itertools;
-use crate::codegen::common::{generate_conditional_bind, insertion_binding, OrmliteCodegen};
+use crate::codegen::common::{
+    acquire_conn, call_hook, commit_conn, generate_conditional_bind, insertion_binding, validate_columns, OrmliteCodegen,
+};
 use crate::MetadataCache;
 use ormlite_attr::ColumnMeta;
 use ormlite_attr::Ident;
@@ -15,8 +17,10 @@ pub fn impl_Model__insert(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_ca
     let db = db.database_ts();
     let table = &attr.name;
     let params = attr.database_columns().map(|c| {
-        if attr.pkey.name == c.name {
+        if attr.is_pkey(c) {
             placeholder.next().unwrap()
+        } else if c.is_timestamp() {
+            "CURRENT_TIMESTAMP".to_string()
         } else if c.has_database_default {
             "DEFAULT".to_string()
         } else {
@@ -26,7 +30,7 @@ pub fn impl_Model__insert(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_ca
 
     let query_bindings = attr
         .database_columns()
-        .filter(|c| attr.pkey.name == c.name || !c.has_database_default)
+        .filter(|c| attr.is_pkey(c) || !c.has_database_default)
         .map(|c| insertion_binding(c));
 
     let insert_join = attr.many_to_one_joins().map(|c| insert_join(c));
@@ -38,6 +42,12 @@ pub fn impl_Model__insert(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_ca
         }
     });
 
+    let before_insert = call_hook(&db, attr, "before_insert", quote! { &mut model });
+    let after_insert = call_hook(&db, attr, "after_insert", quote! { &mut model });
+    let validate = validate_columns(attr.database_columns(), quote! { model });
+    let acquire = acquire_conn(attr, quote! { conn });
+    let commit = commit_conn(attr);
+
     quote! {
         #[allow(unused_mut)]
         fn insert<'a, A>(mut self, conn: A) -> ::ormlite::__private::Insertion<'a, A, Self, #db>
@@ -49,7 +59,9 @@ pub fn impl_Model__insert(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_ca
                 model: self,
                 closure: Box::new(|conn, mut model, query| {
                     Box::pin(async move {
-                        let mut conn = conn.acquire().await?;
+                        #acquire
+                        #before_insert
+                        #validate
                         #(
                             #insert_join
                         )*
@@ -61,6 +73,8 @@ pub fn impl_Model__insert(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_ca
                         #(
                             #late_bind
                         )*
+                        #after_insert
+                        #commit
                         Ok(model)
                     })
                 }),
@@ -81,23 +95,43 @@ pub fn impl_ModelBuilder__insert(db: &dyn OrmliteCodegen, attr: &TableMeta) -> T
     let query = format!("INSERT INTO \"{}\" ({{}}) VALUES ({{}}) RETURNING *", attr.name);
 
     let bind_parameters = attr.database_columns().map(generate_conditional_bind);
+    let timestamps = attr.database_columns().filter(|c| c.is_timestamp()).map(|c| &c.name);
+
+    let before_insert = call_hook(&db, attr, "before_insert_partial", quote! { &mut self });
+    let validate = validate_columns(attr.database_columns(), quote! { self });
+    let after_insert = call_hook(&db, attr, "after_insert", quote! { &mut model });
+    let acquire = acquire_conn(attr, quote! { db });
+    let commit = commit_conn(attr);
 
     quote! {
-        fn insert<'e: 'a, E>(self, db: E) -> #box_future<'a, ::ormlite::Result<Self::Model>>
+        #[allow(unused_mut)]
+        fn insert<A>(mut self, db: A) -> #box_future<'a, ::ormlite::Result<Self::Model>>
         where
-            E: 'e +::ormlite::Executor<'e, Database = #db>,
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>,
         {
             Box::pin(async move {
+                #acquire
+                #before_insert
+                #validate
                 let mut placeholder = #placeholder;
-                let set_fields = self.modified_fields();
+                let mut set_fields = self.modified_fields();
+                let mut values = set_fields.iter().map(|_| placeholder.next().unwrap()).collect::<Vec<_>>();
+                for timestamp in [#(#timestamps,)*] {
+                    if !set_fields.contains(&timestamp) {
+                        set_fields.push(timestamp);
+                        values.push("CURRENT_TIMESTAMP".to_string());
+                    }
+                }
                 let query = format!(
                     #query,
                     set_fields.join(", "),
-                    set_fields.iter().map(|_| placeholder.next().unwrap()).collect::<Vec<_>>().join(", "),
+                    values.join(", "),
                 );
                 let mut q = ::ormlite::query_as::<#db, Self::Model>(&query);
                 #(#bind_parameters)*
-                let model = q.fetch_one(db).await?;
+                let mut model = q.fetch_one(&mut *conn).await?;
+                #after_insert
+                #commit
                 Ok(model)
             })
         }
@@ -117,14 +151,17 @@ pub fn impl_Insert(db: &dyn OrmliteCodegen, meta: &TableMeta, model: &Ident, ret
     let db = db.database_ts();
     let fields = meta
         .database_columns()
-        .filter(|&c| !c.has_database_default)
+        .filter(|&c| !c.has_database_default || c.is_timestamp())
         .map(|c| c.name.clone())
         .collect::<Vec<_>>()
         .join(",");
     let placeholders = meta
         .database_columns()
-        .filter(|&c| !c.has_database_default)
-        .map(|_| placeholder.next().unwrap())
+        .filter(|&c| !c.has_database_default || c.is_timestamp())
+        .map(|c| match c.is_timestamp() {
+            true => "CURRENT_TIMESTAMP".to_string(),
+            false => placeholder.next().unwrap(),
+        })
         .collect::<Vec<_>>()
         .join(",");
     let query = format!(
@@ -151,6 +188,11 @@ pub fn impl_Insert(db: &dyn OrmliteCodegen, meta: &TableMeta, model: &Ident, ret
         }
     });
 
+    let validate = validate_columns(
+        meta.database_columns().filter(|c| !c.has_database_default),
+        quote! { model },
+    );
+
     quote! {
         impl ::ormlite::model::Insert<#db> for #model {
             type Model = #returns;
@@ -164,6 +206,7 @@ pub fn impl_Insert(db: &dyn OrmliteCodegen, meta: &TableMeta, model: &Ident, ret
                     let mut conn = db.acquire().await?;
                     let mut q =::ormlite::query_as::<#db, Self::Model>(#query);
                     let mut model = self;
+                    #validate
                     #(#insert_join)*
                     #(#query_bindings)*
                     let mut model: #returns = q.fetch_one(&mut *conn).await?;
@@ -186,7 +229,7 @@ pub fn insert_join(c: &ColumnMeta) -> TokenStream {
     let preexisting = match joined_ty {
         Type::Option(joined_ty) => {
             quote! {
-                if let Some(id) = model.#id._id() {
+                if let Some(id) = join._id() {
                     #joined_ty::fetch_one(id, &mut *conn).await?
                 } else {
                     None
@@ -195,13 +238,13 @@ pub fn insert_join(c: &ColumnMeta) -> TokenStream {
         }
         joined_ty => {
             quote! {
-                #joined_ty::fetch_one(model.#id._id(), &mut *conn).await?
+                #joined_ty::fetch_one(join._id(), &mut *conn).await?
             }
         }
     };
 
-    quote! {
-        let #id = if let Some(modification) = model.#id._take_modification() {
+    let insert = quote! {
+        if let Some(modification) = join._take_modification() {
             match modification
                     .insert(&mut *conn)
                     .on_conflict(::ormlite::query_builder::OnConflict::Ignore)
@@ -214,8 +257,23 @@ pub fn insert_join(c: &ColumnMeta) -> TokenStream {
                 Err(e) => return Err(e),
             }
         } else {
-            model.#id
-        };
+            join
+        }
+    };
+    if c.is_option() {
+        quote! {
+            let #id = match model.#id {
+                Some(mut join) => Some(#insert),
+                None => None,
+            };
+        }
+    } else {
+        quote! {
+            let #id = {
+                let mut join = model.#id;
+                #insert
+            };
+        }
     }
 }
 
@@ -224,8 +282,10 @@ pub fn impl_Model__insert_many(db: &dyn OrmliteCodegen, meta: &ModelMeta, _mc: &
     let placeholder = db.placeholder_ts();
     let dialect = db.dialect_ts();
     let db = db.database_ts();
+    // Whether each column is a timestamp, which is set to the current time instead of bound.
+    let timestamps = meta.database_columns().map(|c| c.is_timestamp());
 
-    let query_bindings = meta.database_columns().map(|c| {
+    let query_bindings = meta.database_columns().filter(|c| !c.is_timestamp()).map(|c| {
         if let Some(rust_default) = &c.rust_default {
             let default: syn::Expr = syn::parse_str(&rust_default).expect("Failed to parse default_value");
             quote! {
@@ -241,18 +301,39 @@ pub fn impl_Model__insert_many(db: &dyn OrmliteCodegen, meta: &ModelMeta, _mc: &
         }
     }).collect_vec();
 
+    let before_insert = call_hook(&db, meta, "before_insert", quote! { model });
+    let before_insert = meta.hooks.then(|| quote! { for model in &mut values { #before_insert } });
+    let after_insert = call_hook(&db, meta, "after_insert", quote! { model });
+    let after_insert = meta.hooks.then(|| quote! { for model in &mut models { #after_insert } });
+    let validate = meta.database_columns().any(|c| !c.validation.is_empty()).then(|| {
+        let validate = validate_columns(meta.database_columns(), quote! { model });
+        quote! { for model in &values { #validate } }
+    });
+    let acquire = acquire_conn(meta, quote! { db });
+    let commit = commit_conn(meta);
+
     quote! {
-        fn insert_many<'e, E>(values: Vec<Self>, db: E) -> #box_future<'e, ::ormlite::Result<Vec<Self>>>
+        #[allow(unused_mut)]
+        fn insert_many<'a, A>(mut values: Vec<Self>, db: A) -> #box_future<'a, ::ormlite::Result<Vec<Self>>>
         where
-            E: 'e + ::ormlite::Executor<'e, Database = #db>,
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>,
         {
             Box::pin(async move {
+                #acquire
+                #before_insert
+                #validate
                 let table = <Self as ::ormlite::TableMeta>::table_name();
                 let columns = <Self as ::ormlite::TableMeta>::table_columns();
+                let mut placeholder = #placeholder;
                 let mut sql_values = ::ormlite::__private::Values::Values(Vec::new());
                 for _ in 0..values.len() {
                     let mut value = ::ormlite::__private::Value::new();
-                    value = value.placeholders(columns.len(), #dialect);
+                    for timestamp in [#(#timestamps,)*] {
+                        value = match timestamp {
+                            true => value.column("CURRENT_TIMESTAMP"),
+                            false => value.column(&placeholder.next().unwrap()),
+                        };
+                    }
                     sql_values = sql_values.value(value);
                 }
                 let sql = ::ormlite::__private::Insert::new(table)
@@ -264,7 +345,10 @@ pub fn impl_Model__insert_many(db: &dyn OrmliteCodegen, meta: &ModelMeta, _mc: &
                 for model in values {
                     #(#query_bindings)*
                 }
-                q.fetch_all(db).await.map_err(Into::into)
+                let mut models = q.fetch_all(&mut *conn).await?;
+                #after_insert
+                #commit
+                Ok(models)
             })
         }
     }
@@ -10,7 +10,7 @@ pub fn struct_InsertModel(ast: &DeriveInput, attr: &ModelMeta) -> TokenStream {
         return quote! {};
     };
     let vis = &ast.vis;
-    let struct_fields = attr.columns.iter().filter(|c| !c.is_default()).map(|c| {
+    let struct_fields = attr.columns.iter().filter(|c| !c.is_default() && !c.is_join_many()).map(|c| {
         let id = &c.ident;
         let ty = &c.ty;
         quote! {
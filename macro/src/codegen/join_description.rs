@@ -1,5 +1,5 @@
 use crate::MetadataCache;
-use ormlite_attr::TableMeta;
+use ormlite_attr::{ColumnMeta, ModelMeta, TableMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -13,11 +13,15 @@ pub fn static_join_descriptions(attr: &TableMeta, metadata_cache: &MetadataCache
             .get(&struct_name)
             .expect(&format!("Did not find metadata for joined struct: {}", struct_name));
         let foreign_table = &joined_table.table.name;
-        let foreign_key = &joined_table.pkey.name;
+        let foreign_key = &joined_table.pkey().name;
 
         let columns = joined_table.database_columns().map(|c| &c.name);
         let body = match join {
             ormlite_attr::Join::ManyToOne { column } => {
+                let soft_delete_column = match &joined_table.soft_delete {
+                    Some(c) => quote! { Some(#c) },
+                    None => quote! { None },
+                };
                 quote! {
                     ::ormlite::__private::JoinDescription::ManyToOne {
                         columns: &[
@@ -29,11 +33,42 @@ pub fn static_join_descriptions(attr: &TableMeta, metadata_cache: &MetadataCache
                         local_column: #column,
                         field: #field,
                         foreign_key: #foreign_key,
+                        soft_delete_column: #soft_delete_column,
+                    }
+                }
+            }
+            ormlite_attr::Join::ManyToMany { .. } => {
+                let (join_table, join_table_local_key, join_table_foreign_key) =
+                    c.join_table_columns(attr, joined_table).unwrap();
+                let [local_column] = attr.pkeys.as_slice() else {
+                    panic!("A many to many join requires a single column primary key on the local model");
+                };
+                quote! {
+                    ::ormlite::__private::JoinDescription::ManyToMany {
+                        foreign_table: #foreign_table,
+                        local_column: #local_column,
+                        field: #field,
+                        foreign_key: #foreign_key,
+                        join_table: #join_table,
+                        join_table_local_key: #join_table_local_key,
+                        join_table_foreign_key: #join_table_foreign_key,
+                    }
+                }
+            }
+            ormlite_attr::Join::OneToMany { field: foreign_field, .. } => {
+                let foreign_key = &foreign_column(joined_table, foreign_field).name;
+                let [local_column] = attr.pkeys.as_slice() else {
+                    panic!("A one to many join requires a single column primary key on the local model");
+                };
+                quote! {
+                    ::ormlite::__private::JoinDescription::OneToMany {
+                        foreign_table: #foreign_table,
+                        local_column: #local_column,
+                        field: #field,
+                        foreign_key: #foreign_key,
                     }
                 }
             }
-            ormlite_attr::Join::ManyToMany { table } => todo!(),
-            ormlite_attr::Join::OneToMany { model, field } => todo!(),
         };
         let ident = &c.ident;
         quote! {
@@ -52,3 +87,13 @@ pub fn static_join_descriptions(attr: &TableMeta, metadata_cache: &MetadataCache
         }
     }
 }
+
+/// Find the field on the joined model named by `#[ormlite(foreign_field = Model::field)]`.
+pub fn foreign_column<'a>(joined_table: &'a ModelMeta, field: &str) -> &'a ColumnMeta {
+    joined_table.columns.iter().find(|c| c.ident == field).unwrap_or_else(|| {
+        panic!(
+            "foreign_field refers to {}::{}, but {} has no field named {}",
+            joined_table.ident, field, joined_table.ident, field
+        )
+    })
+}
@@ -0,0 +1,165 @@
+use crate::codegen::common::OrmliteCodegen;
+use crate::codegen::join_description::foreign_column;
+use crate::MetadataCache;
+use ormlite_attr::{ColumnMeta, ModelMeta, Type};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Loads a join into a batch of models. Used for the joins `SelectQueryBuilder` defers until after the main
+/// query, and by `LoadAll`.
+pub fn impl_Model__load_join(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_cache: &MetadataCache) -> TokenStream {
+    let box_future = crate::util::box_fut_ts();
+    let model = &attr.ident;
+    let branches = attr.columns.iter().filter_map(|c| match c.join.as_ref()? {
+        ormlite_attr::Join::OneToMany { field, .. } => Some(load_one_to_many(db, c, field, metadata_cache)),
+        ormlite_attr::Join::ManyToMany { .. } => Some(load_many_to_many(db, c)),
+        ormlite_attr::Join::ManyToOne { .. } => Some(load_many_to_one(db, c, metadata_cache)),
+        _ => None,
+    });
+    let branches = branches.collect::<Vec<_>>();
+    if branches.is_empty() {
+        return TokenStream::new();
+    }
+    let db = db.database_ts();
+    quote! {
+        fn _load_join<'a>(
+            models: &'a mut [Self],
+            join: ::ormlite::__private::JoinDescription,
+            conn: &'a mut <#db as ::ormlite::Database>::Connection,
+        ) -> #box_future<'a, ::ormlite::Result<()>> {
+            Box::pin(async move {
+                match join.field() {
+                    #(#branches)*
+                    field => Err(::ormlite::Error::OrmliteError(format!(
+                        "{} does not know how to load the join `{}`",
+                        stringify!(#model),
+                        field
+                    ))),
+                }
+            })
+        }
+    }
+}
+
+/// Assumed bindings:
+/// - `models`: the models to load the join into
+/// - `conn`: the connection to run the query on
+fn load_many_to_one(db: &dyn OrmliteCodegen, c: &ColumnMeta, metadata_cache: &MetadataCache) -> TokenStream {
+    let field = c.ident.to_string();
+    let id = &c.ident;
+    let struct_name = c.joined_struct_name().unwrap();
+    let joined_table = metadata_cache
+        .get(&struct_name)
+        .unwrap_or_else(|| panic!("Did not find metadata for joined struct: {}", struct_name));
+    let joined_model = c.joined_model();
+    let foreign_key = &joined_table.pkey().name;
+    let join = if c.is_option() {
+        quote! { Some(::ormlite::model::Join::_query_result(related)) }
+    } else {
+        quote! { ::ormlite::model::Join::_query_result(related) }
+    };
+    let db = db.database_ts();
+    quote! {
+        #field => {
+            let ids = models
+                .iter()
+                .map(|model| ::ormlite::model::JoinMeta::_id(&model.#id))
+                .collect::<Vec<_>>();
+            let related = ::ormlite::query_builder::SelectQueryBuilder::<#db, #joined_model>::_fetch_for_ids(
+                #foreign_key,
+                ids,
+                &mut *conn,
+            )
+            .await?;
+            for (model, related) in models.iter_mut().zip(related) {
+                if let Some(related) = related {
+                    model.#id = #join;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Assumed bindings:
+/// - `models`: the models to load the join into
+/// - `conn`: the connection to run the query on
+fn load_one_to_many(
+    db: &dyn OrmliteCodegen,
+    c: &ColumnMeta,
+    foreign_field: &str,
+    metadata_cache: &MetadataCache,
+) -> TokenStream {
+    let field = c.ident.to_string();
+    let id = &c.ident;
+    let struct_name = c.joined_struct_name().unwrap();
+    let joined_table = metadata_cache
+        .get(&struct_name)
+        .unwrap_or_else(|| panic!("Did not find metadata for joined struct: {}", struct_name));
+    let joined_model = c.joined_model();
+    let foreign_column = foreign_column(joined_table, foreign_field);
+    let foreign_key = &foreign_column.name;
+    let foreign_ident = &foreign_column.ident;
+    // The foreign field either holds the id directly, optionally, or as a many to one join back to this model.
+    let key = match &foreign_column.ty {
+        Type::Join(_) => quote! { Some(::ormlite::model::JoinMeta::_id(&related.#foreign_ident)) },
+        Type::Option(ty) if ty.is_join() => quote! { ::ormlite::model::JoinMeta::_id(&related.#foreign_ident) },
+        Type::Option(_) => quote! { related.#foreign_ident.clone() },
+        _ => quote! { Some(related.#foreign_ident.clone()) },
+    };
+    let db = db.database_ts();
+    quote! {
+        #field => {
+            let ids = models.iter().map(::ormlite::model::JoinMeta::_id).collect::<Vec<_>>();
+            let mut grouped = ::std::collections::HashMap::<_, Vec<#joined_model>>::new();
+            let related = ::ormlite::query_builder::SelectQueryBuilder::<#db, #joined_model>::_fetch_where_in(
+                #foreign_key,
+                ids,
+                &mut *conn,
+            )
+            .await?;
+            for related in related {
+                if let Some(key) = #key {
+                    grouped.entry(key).or_default().push(related);
+                }
+            }
+            for model in models.iter_mut() {
+                let related = grouped.remove(&::ormlite::model::JoinMeta::_id(model)).unwrap_or_default();
+                model.#id = ::ormlite::model::Join::_query_result(related);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Assumed bindings:
+/// - `models`: the models to load the join into
+/// - `join`: the join description, which names the join table
+/// - `conn`: the connection to run the query on
+fn load_many_to_many(db: &dyn OrmliteCodegen, c: &ColumnMeta) -> TokenStream {
+    let field = c.ident.to_string();
+    let id = &c.ident;
+    let joined_model = c.joined_model();
+    let db = db.database_ts();
+    quote! {
+        #field => {
+            let ids = models.iter().map(::ormlite::model::JoinMeta::_id).collect::<Vec<_>>();
+            let mut grouped = ::std::collections::HashMap::<_, Vec<#joined_model>>::new();
+            let related =
+                ::ormlite::query_builder::SelectQueryBuilder::<#db, #joined_model>::_fetch_all_through(
+                    join,
+                    ids,
+                    &mut *conn,
+                )
+                .await?;
+            for (key, related) in related {
+                grouped.entry(key).or_default().push(related);
+            }
+            for model in models.iter_mut() {
+                let related = grouped.remove(&::ormlite::model::JoinMeta::_id(model)).unwrap_or_default();
+                model.#id = ::ormlite::model::Join::_query_result(related);
+            }
+            Ok(())
+        }
+    }
+}
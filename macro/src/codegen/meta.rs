@@ -3,16 +3,43 @@ use ormlite_attr::TableMeta;
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub fn impl_TableMeta(table: &TableMeta, pkey: Option<&str>) -> TokenStream {
+pub fn impl_TableMeta(table: &TableMeta) -> TokenStream {
     let ident = &table.ident;
     let table_name = &table.name;
-    let id = match pkey {
-        Some(id) => quote! { Some(#id) },
-        None => quote! { None },
+    let id = match table.pkeys.as_slice() {
+        [id] => quote! { Some(#id) },
+        _ => quote! { None },
     };
+    let pkeys = &table.pkeys;
 
     let field_names = table.database_columns().map(|c| c.name.to_string());
 
+    let updated_at_column = table.updated_at().map(|c| {
+        let name = &c.name;
+        quote! {
+            fn updated_at_column() -> Option<&'static str> {
+                Some(#name)
+            }
+        }
+    });
+
+    let version_column = table.version().map(|c| {
+        let name = &c.name;
+        quote! {
+            fn version_column() -> Option<&'static str> {
+                Some(#name)
+            }
+        }
+    });
+
+    let soft_delete_column = table.soft_delete.as_ref().map(|name| {
+        quote! {
+            fn soft_delete_column() -> Option<&'static str> {
+                Some(#name)
+            }
+        }
+    });
+
     quote! {
         impl ::ormlite::model::TableMeta for #ident {
             fn table_name() -> &'static str {
@@ -26,21 +53,38 @@ pub fn impl_TableMeta(table: &TableMeta, pkey: Option<&str>) -> TokenStream {
             fn primary_key() -> Option<&'static str> {
                 #id
             }
+
+            fn primary_key_columns() -> &'static [&'static str] {
+                &[#(#pkeys,)*]
+            }
+
+            #updated_at_column
+            #version_column
+            #soft_delete_column
         }
     }
 }
 
 pub fn impl_JoinMeta(attr: &ModelMeta) -> TokenStream {
     let model = &attr.ident;
-    let id_type = &attr.pkey.ty;
-    let id = &attr.pkey.ident;
+    let (id_type, id) = match attr.pkeys.as_slice() {
+        [pkey] => {
+            let (ty, id) = (&pkey.ty, &pkey.ident);
+            // clone is identical to Copy for most id types, but lets us use cloneable types like String.
+            (quote! { #ty }, quote! { self.#id.clone() })
+        }
+        pkeys => {
+            let tys = pkeys.iter().map(|c| &c.ty);
+            let ids = pkeys.iter().map(|c| &c.ident);
+            (quote! { (#(#tys,)*) }, quote! { (#(self.#ids.clone(),)*) })
+        }
+    };
 
     quote! {
         impl ::ormlite::model::JoinMeta for #model {
             type IdType = #id_type;
             fn _id(&self) -> Self::IdType {
-                // clone is identical to Copy for most id types, but lets us use cloneable types like String.
-                self.#id.clone()
+                #id
             }
         }
     }
@@ -1,9 +1,11 @@
-use crate::codegen::common::OrmliteCodegen;
+use crate::codegen::common::{acquire_conn, call_hook, commit_conn, pkey_condition, OrmliteCodegen};
 use crate::codegen::insert::{impl_Model__insert, impl_Model__insert_many};
+use crate::codegen::load_join::impl_Model__load_join;
 use crate::codegen::select::impl_Model__select;
 use crate::codegen::update::impl_Model__update_all_fields;
 use crate::MetadataCache;
 use ormlite_attr::ModelMeta;
+use ormlite_core::query_builder::Placeholder;
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -15,19 +17,30 @@ pub fn impl_Model(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_cache: &Me
     let impl_Model__insert_many = impl_Model__insert_many(db, &attr, metadata_cache);
     let impl_Model__update_all_fields = impl_Model__update_all_fields(db, attr);
     let impl_Model__delete = impl_Model__delete(db, attr);
+    let impl_Model__delete_by_id = impl_Model__delete_by_id(db, attr);
+    let impl_Model__restore = impl_Model__restore(db, attr);
     let impl_Model__fetch_one = impl_Model__fetch_one(db, attr);
     let impl_Model__select = impl_Model__select(db, &attr.table);
     let impl_Model__builder = impl_Model__builder(attr);
     let impl_Model__update_partial = impl_Model__update_partial(attr);
+    let impl_Model__load_join = impl_Model__load_join(db, attr, metadata_cache);
+    let primary_key_kind = if attr.pkeys.len() > 1 {
+        quote! { ::ormlite::model::Composite }
+    } else {
+        quote! { ::ormlite::model::Single }
+    };
     let db = db.database_ts();
     quote! {
         impl ::ormlite::model::Model<#db> for #model {
             type ModelBuilder<'a> = #partial_model<'a> where Self: 'a;
+            type PrimaryKeyKind = #primary_key_kind;
 
             #impl_Model__insert
             #impl_Model__insert_many
             #impl_Model__update_all_fields
             #impl_Model__delete
+            #impl_Model__delete_by_id
+            #impl_Model__restore
             #impl_Model__fetch_one
             #impl_Model__select
 
@@ -37,67 +50,142 @@ pub fn impl_Model(db: &dyn OrmliteCodegen, attr: &ModelMeta, metadata_cache: &Me
 
             #impl_Model__builder
             #impl_Model__update_partial
-
+            #impl_Model__load_join
         }
     }
 }
 
+/// Delete by primary key, or mark the row deleted if the model is soft deleted.
+fn delete_query(attr: &ModelMeta, placeholder: &mut Placeholder) -> String {
+    let condition = pkey_condition(attr, placeholder);
+    match &attr.soft_delete {
+        Some(column) => format!(
+            "UPDATE \"{}\" SET \"{column}\" = CURRENT_TIMESTAMP WHERE {condition} AND \"{column}\" IS NULL",
+            attr.name
+        ),
+        None => format!("DELETE FROM \"{}\" WHERE {condition}", attr.name),
+    }
+}
+
 pub fn impl_Model__delete(db: &dyn OrmliteCodegen, attr: &ModelMeta) -> TokenStream {
     let mut placeholder = db.placeholder();
 
-    let query = format!(
-        "DELETE FROM \"{}\" WHERE {} = {}",
-        attr.name,
-        attr.pkey.name,
-        placeholder.next().unwrap()
-    );
+    let query = delete_query(attr, &mut placeholder);
 
     let box_future = crate::util::box_fut_ts();
     let db = db.database_ts();
-    let id = &attr.pkey.ident;
+    let ids = attr.pkeys.iter().map(|c| &c.ident);
+    let before_delete = call_hook(&db, attr, "before_delete", quote! { &mut model });
+    let acquire = acquire_conn(attr, quote! { db });
+    let commit = commit_conn(attr);
     quote! {
-        fn delete<'e, E>(self, db: E) -> #box_future<'e, ::ormlite::Result<()>>
+        #[allow(unused_mut)]
+        fn delete<'a, A>(self, db: A) -> #box_future<'a, ::ormlite::Result<()>>
         where
-            E: 'e +::ormlite::Executor<'e, Database = #db>
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>
         {
             Box::pin(async move {
+                #acquire
+                let mut model = self;
+                #before_delete
                 let row =::ormlite::query(#query)
-                    .bind(self.#id)
-                    .execute(db)
+                    #(.bind(model.#ids))*
+                    .execute(&mut *conn)
                     .await
                     .map_err(::ormlite::Error::from)?;
                 if row.rows_affected() == 0 {
-                    Err(::ormlite::Error::from(::ormlite::SqlxError::RowNotFound))
-                } else {
-                    Ok(())
+                    return Err(::ormlite::Error::from(::ormlite::SqlxError::RowNotFound));
                 }
+                #commit
+                Ok(())
             })
         }
     }
 }
 
-pub fn impl_Model__fetch_one(db: &dyn OrmliteCodegen, attr: &ModelMeta) -> TokenStream {
+pub fn impl_Model__delete_by_id(db: &dyn OrmliteCodegen, attr: &ModelMeta) -> TokenStream {
+    let mut placeholder = db.placeholder();
+
+    let query = delete_query(attr, &mut placeholder);
+
+    let db = db.database_ts();
+    let box_future = crate::util::box_fut_ts();
+    quote! {
+        fn delete_by_id<'e, 'a, Arg, E>(id: Arg, db: E) -> #box_future<'e, ::ormlite::Result<u64>>
+        where
+            'a: 'e,
+            Arg: ::ormlite::model::PrimaryKey<'a, #db, Self::PrimaryKeyKind>,
+            E: 'e +::ormlite::Executor<'e, Database = #db>
+        {
+            Box::pin(async move {
+                let mut args = ::ormlite::query_builder::QueryBuilderArgs::default();
+                ::ormlite::model::PrimaryKey::add_to(id, &mut args);
+                let result = ::ormlite::query_with::<#db, _>(#query, args)
+                    .execute(db)
+                    .await
+                    .map_err(::ormlite::Error::from)?;
+                Ok(result.rows_affected())
+            })
+        }
+    }
+}
+
+pub fn impl_Model__restore(db: &dyn OrmliteCodegen, attr: &ModelMeta) -> TokenStream {
+    let Some(column) = &attr.soft_delete else {
+        return TokenStream::new();
+    };
     let mut placeholder = db.placeholder();
 
     let query = format!(
-        "SELECT * FROM \"{}\" WHERE {} = {}",
+        "UPDATE \"{}\" SET \"{column}\" = NULL WHERE {} RETURNING *",
         attr.name,
-        attr.pkey.name,
-        placeholder.next().unwrap()
+        pkey_condition(attr, &mut placeholder)
     );
 
+    let box_future = crate::util::box_fut_ts();
+    let db = db.database_ts();
+    let ids = attr.pkeys.iter().map(|c| &c.ident);
+    quote! {
+        fn restore<'e, E>(self, db: E) -> #box_future<'e, ::ormlite::Result<Self>>
+        where
+            E: 'e +::ormlite::Executor<'e, Database = #db>
+        {
+            Box::pin(async move {
+                ::ormlite::query_as::<_, Self>(#query)
+                    #(.bind(self.#ids))*
+                    .fetch_one(db)
+                    .await
+                    .map_err(::ormlite::Error::from)
+            })
+        }
+    }
+}
+
+pub fn impl_Model__fetch_one(db: &dyn OrmliteCodegen, attr: &ModelMeta) -> TokenStream {
+    let mut placeholder = db.placeholder();
+
+    let mut query = format!(
+        "SELECT * FROM \"{}\" WHERE {}",
+        attr.name,
+        pkey_condition(attr, &mut placeholder)
+    );
+    if let Some(column) = &attr.soft_delete {
+        query.push_str(&format!(" AND \"{column}\" IS NULL"));
+    }
+
     let db = db.database_ts();
     let box_future = crate::util::box_fut_ts();
     quote! {
         fn fetch_one<'e, 'a, Arg, E>(id: Arg, db: E) -> #box_future<'e, ::ormlite::Result<Self>>
         where
             'a: 'e,
-            Arg: 'a + Send + ::ormlite::Encode<'a, #db> + ::ormlite::types::Type<#db>,
+            Arg: ::ormlite::model::PrimaryKey<'a, #db, Self::PrimaryKeyKind>,
             E: 'e +::ormlite::Executor<'e, Database = #db>
         {
             Box::pin(async move {
-                ::ormlite::query_as::<#db, Self>(#query)
-                    .bind(id)
+                let mut args = ::ormlite::query_builder::QueryBuilderArgs::default();
+                ::ormlite::model::PrimaryKey::add_to(id, &mut args);
+                ::ormlite::query_as_with::<#db, Self, _>(#query, args)
                     .fetch_one(db)
                     .await
                     .map_err(::ormlite::Error::from)
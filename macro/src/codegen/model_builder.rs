@@ -1,4 +1,4 @@
-use crate::codegen::common::OrmliteCodegen;
+use crate::codegen::common::{validate_columns, OrmliteCodegen};
 use crate::codegen::insert::impl_ModelBuilder__insert;
 use crate::codegen::update::impl_ModelBuilder__update;
 use ormlite_attr::ModelMeta;
@@ -76,20 +76,23 @@ pub fn impl_ModelBuilder__build(attr: &TableMeta) -> TokenStream {
 
     let fields = attr.database_columns().map(|c| &c.ident);
 
-    let skipped_fields = attr.columns.iter().filter(|&c| c.skip).map(|c| {
+    let skipped_fields = attr.columns.iter().filter(|&c| c.skip || c.is_join_many()).map(|c| {
         let id = &c.ident;
         quote! {
             #id: Default::default()
         }
     });
 
+    let validate = validate_columns(attr.database_columns(), quote! { self });
+
     quote! {
-        fn build(self) -> Self::Model {
+        fn build(self) -> ::ormlite::Result<Self::Model> {
+            #validate
             #( #unpack )*
-            Self::Model {
+            Ok(Self::Model {
                 #( #fields, )*
                 #( #skipped_fields, )*
-            }
+            })
         }
     }
 }
@@ -1,5 +1,9 @@
-use crate::codegen::common::{generate_conditional_bind, insertion_binding, OrmliteCodegen};
-use ormlite_attr::ModelMeta;
+use crate::codegen::common::{
+    acquire_conn, call_hook, commit_conn, generate_conditional_bind, insertion_binding, pkey_condition,
+    validate_columns, OrmliteCodegen,
+};
+use itertools::Itertools;
+use ormlite_attr::{ColumnMeta, ModelMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -10,22 +14,36 @@ pub fn impl_Model__update_all_fields(db: &dyn OrmliteCodegen, attr: &ModelMeta)
     let mut query = "UPDATE \"".to_string();
     query.push_str(&attr.name);
     query.push_str("\" SET ");
-    for c in attr.database_columns_except_pkey() {
+    // created_at keeps its value, updated_at is set to the current time, and the version is incremented.
+    let updated_columns = || attr.database_columns_except_pkey().filter(|c| !c.created_at);
+    for c in updated_columns() {
         query.push_str(&c.name);
         query.push_str(" = ");
-        query.push_str(&placeholder.next().unwrap());
+        if c.updated_at {
+            query.push_str("CURRENT_TIMESTAMP");
+        } else if c.version {
+            query.push_str(&c.name);
+            query.push_str(" + 1");
+        } else {
+            query.push_str(&placeholder.next().unwrap());
+        }
         query.push_str(", ");
     }
     // remove the final ", "
     query.truncate(query.len() - 2);
     query.push_str(" WHERE ");
-    query.push_str(&attr.pkey.name);
-    query.push_str(" = ");
-    query.push_str(&placeholder.next().unwrap());
+    query.push_str(&pkey_condition(attr, &mut placeholder));
+    if let Some(version) = attr.version() {
+        query.push_str(&format!(" AND {} = {}", version.name, placeholder.next().unwrap()));
+    }
     query.push_str(" RETURNING *");
 
-    let id = &attr.pkey.ident;
-    let query_bindings = attr.database_columns_except_pkey().map(|c| insertion_binding(c));
+    let ids = attr.pkeys.iter().map(|c| &c.ident).collect::<Vec<_>>();
+    let version = attr.version().map(|c| &c.ident).into_iter().collect::<Vec<_>>();
+    let query_bindings = updated_columns()
+        .filter(|c| !c.updated_at && !c.version)
+        .map(|c| insertion_binding(c))
+        .collect::<Vec<_>>();
 
     let unwind_joins = attr.many_to_one_joins().map(|c| {
         let id = &c.ident;
@@ -34,20 +52,77 @@ pub fn impl_Model__update_all_fields(db: &dyn OrmliteCodegen, attr: &ModelMeta)
         }
     });
 
+    let update_joins = attr.many_to_one_joins().map(|c| {
+        let id = &c.ident;
+        let update = update_join(c, &db);
+        quote! {
+            let join = &mut model.#id;
+            #update
+        }
+    });
+
+    let move_joins = attr.many_to_one_joins().map(|c| {
+        let id = &c.ident;
+        quote! {
+            let #id = model.#id;
+        }
+    });
+
+    let late_bind = attr.many_to_one_joins().map(|c| {
+        let id = &c.ident;
+        quote! {
+            updated.#id = #id;
+        }
+    });
+
+    let fetch = fetch_updated(attr);
+    let before_update = call_hook(&db, attr, "before_update", quote! { &mut model });
+    let validate = validate_columns(attr.database_columns(), quote! { model });
+    let acquire = acquire_conn(attr, quote! { db });
+    let commit = commit_conn(attr);
+
     quote! {
-        fn update_all_fields<'e, E>(self, db: E) -> #box_future<'e, ::ormlite::Result<Self>>
+        #[allow(unused_mut)]
+        fn update_all_fields<'a, A>(self, db: A) -> #box_future<'a, ::ormlite::Result<Self>>
         where
-            E: 'e +::ormlite::Executor<'e, Database = #db>,
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>,
         {
             Box::pin(async move {
+                #acquire
+                let mut model = self;
+                #before_update
+                #validate
                 let mut q =::ormlite::query_as::<_, Self>(#query);
-                let model = self;
                 #(#unwind_joins)*
                 #(#query_bindings)*
-                q.bind(model.#id)
-                    .fetch_one(db)
-                    .await
-                    .map_err(::ormlite::Error::from)
+                #(q = q.bind(model.#ids);)*
+                #(q = q.bind(model.#version);)*
+                let updated = #fetch?;
+                #commit
+                Ok(updated)
+            })
+        }
+
+        #[allow(unused_mut)]
+        fn update_all_fields_cascade<'a, A>(self, db: A) -> #box_future<'a, ::ormlite::Result<Self>>
+        where
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>,
+        {
+            Box::pin(async move {
+                let mut conn = db.begin().await?;
+                let mut model = self;
+                #before_update
+                #validate
+                #(#update_joins)*
+                let mut q =::ormlite::query_as::<_, Self>(#query);
+                #(#move_joins)*
+                #(#query_bindings)*
+                #(q = q.bind(model.#ids);)*
+                #(q = q.bind(model.#version);)*
+                let mut updated = #fetch?;
+                #(#late_bind)*
+                conn.commit().await?;
+                Ok(updated)
             })
         }
     }
@@ -58,42 +133,193 @@ pub fn impl_ModelBuilder__update(db: &dyn OrmliteCodegen, attr: &ModelMeta) -> T
     let placeholder = db.placeholder_ts();
     let db = db.database_ts();
 
-    let query = format!(
-        "UPDATE \"{}\" SET {{}} WHERE {} = {{}} RETURNING *",
-        attr.name, attr.pkey.name,
-    );
+    // placeholders are numbered at runtime, after the SET clause
+    let mut pkey_condition = attr.pkeys.iter().map(|c| format!("{} = {{}}", c.name)).join(" AND ");
+    if let Some(version) = attr.version() {
+        pkey_condition.push_str(&format!(" AND {} = {{}}", version.name));
+    }
+    let query = format!("UPDATE \"{}\" SET {{}} WHERE {} RETURNING *", attr.name, pkey_condition);
 
     let bind_update = attr.database_columns().map(generate_conditional_bind);
-    let id = &attr.pkey.ident;
+    // Joins are bound by reference, so they can be put on the updated model afterwards.
+    let bind_update_cascade = attr.database_columns().map(|c| {
+        if c.is_join() {
+            let name = &c.ident;
+            quote! {
+                if let Some(value) = &self.#name {
+                    q = q.bind(value._id());
+                }
+            }
+        } else {
+            generate_conditional_bind(c)
+        }
+    });
+
+    let update_joins = attr.many_to_one_joins().map(|c| {
+        let id = &c.ident;
+        let update = update_join(c, &db);
+        quote! {
+            if let Some(join) = &mut self.#id {
+                #update
+            }
+        }
+    });
+
+    let late_bind = attr.many_to_one_joins().map(|c| {
+        let id = &c.ident;
+        quote! {
+            if let Some(join) = self.#id {
+                model.#id = join;
+            }
+        }
+    });
+
+    // updated_at and the version are set, unless the update sets them.
+    let mut automatic = Vec::new();
+    if let Some(updated_at) = attr.updated_at() {
+        let name = &updated_at.name;
+        automatic.push(quote! {
+            if !set_fields.contains(&#name) {
+                set.push(format!("\"{}\" = CURRENT_TIMESTAMP", #name));
+            }
+        });
+    }
+    if let Some(version) = attr.version() {
+        let name = &version.name;
+        automatic.push(quote! {
+            if !set_fields.contains(&#name) {
+                set.push(format!("\"{0}\" = \"{0}\" + 1", #name));
+            }
+        });
+    }
+    let set_clause = if automatic.is_empty() {
+        quote! {
+            set_fields.into_iter().map(|f| format!("\"{}\" = {}", f, placeholder.next().unwrap())).collect::<Vec<_>>().join(", ")
+        }
+    } else {
+        quote! {
+            {
+                let mut set = set_fields.iter().map(|f| format!("\"{}\" = {}", f, placeholder.next().unwrap())).collect::<Vec<_>>();
+                #(#automatic)*
+                set.join(", ")
+            }
+        }
+    };
+
+    let ids = attr.pkeys.iter().map(|c| &c.ident).collect::<Vec<_>>();
+    let version = attr.version().map(|c| &c.ident).into_iter().collect::<Vec<_>>();
+    let pkey_placeholders = ids
+        .iter()
+        .chain(&version)
+        .map(|_| quote! { placeholder.next().unwrap() })
+        .collect::<Vec<_>>();
+    let fetch = fetch_updated(attr);
+    let before_update = call_hook(&db, attr, "before_update_partial", quote! { &mut self });
+    let validate = validate_columns(attr.database_columns(), quote! { self });
+    let acquire = acquire_conn(attr, quote! { db });
+    let commit = commit_conn(attr);
+    let update_id = quote! {
+        self.updating
+            .expect("Tried to call ModelBuilder::update(), but the ModelBuilder \
+            has no reference to what model to update. You might have called \
+            something like: `<Model>::build().update(&mut db)`. A partial update \
+            looks something like \
+            `<model instance>.update_partial().update(&mut db)`.")
+    };
     quote! {
-        fn update<'e: 'a, E>(self, db: E) -> #box_future<'a, ::ormlite::Result<Self::Model>>
+        #[allow(unused_mut)]
+        fn update<A>(mut self, db: A) -> #box_future<'a, ::ormlite::Result<Self::Model>>
         where
-            E: 'e +::ormlite::Executor<'e, Database = #db>,
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>,
         {
             Box::pin(async move {
+                #acquire
+                #before_update
+                #validate
                 let mut placeholder = #placeholder;
                 let set_fields = self.modified_fields();
-                let update_id = self.updating
-                    .expect("Tried to call ModelBuilder::update(), but the ModelBuilder \
-                    has no reference to what model to update. You might have called \
-                    something like: `<Model>::build().update(&mut db)`. A partial update \
-                    looks something like \
-                    `<model instance>.update_partial().update(&mut db)`.")
-                    .#id
-                    // NOTE: This clone is free for Copy types. .clone() fixes ormlite#13
-                    .clone();
+                let updating = #update_id;
                 let query = format!(
                     #query,
-                    set_fields.into_iter().map(|f| format!("\"{}\" = {}", f, placeholder.next().unwrap())).collect::<Vec<_>>().join(", "),
-                    placeholder.next().unwrap()
+                    #set_clause,
+                    #(#pkey_placeholders,)*
                 );
                 let mut q =::ormlite::query_as::<#db, Self::Model>(&query);
                 #(#bind_update)*
-                q = q.bind(update_id);
-                q.fetch_one(db)
-                    .await
-                    .map_err(::ormlite::Error::from)
+                // NOTE: This clone is free for Copy types. .clone() fixes ormlite#13
+                #(q = q.bind(updating.#ids.clone());)*
+                #(q = q.bind(updating.#version.clone());)*
+                let model = #fetch?;
+                #commit
+                Ok(model)
+            })
+        }
+
+        #[allow(unused_mut)]
+        fn update_cascade<A>(mut self, db: A) -> #box_future<'a, ::ormlite::Result<Self::Model>>
+        where
+            A: 'a + Send + ::ormlite::Acquire<'a, Database = #db>,
+        {
+            Box::pin(async move {
+                let mut conn = db.begin().await?;
+                #before_update
+                #validate
+                #(#update_joins)*
+                let mut placeholder = #placeholder;
+                let set_fields = self.modified_fields();
+                let updating = #update_id;
+                let query = format!(
+                    #query,
+                    #set_clause,
+                    #(#pkey_placeholders,)*
+                );
+                let mut q =::ormlite::query_as::<#db, Self::Model>(&query);
+                #(#bind_update_cascade)*
+                // NOTE: This clone is free for Copy types. .clone() fixes ormlite#13
+                #(q = q.bind(updating.#ids.clone());)*
+                #(q = q.bind(updating.#version.clone());)*
+                let mut model = #fetch?;
+                #(#late_bind)*
+                conn.commit().await?;
+                Ok(model)
             })
         }
     }
 }
+
+/// Fetch the updated row of query `q`. With a version column, no row means the model is stale.
+/// Assumed bindings:
+/// - `conn`: a connection, or anything that dereferences to one
+fn fetch_updated(attr: &ModelMeta) -> TokenStream {
+    if attr.version().is_some() {
+        quote! {
+            q.fetch_optional(&mut *conn).await?.ok_or(::ormlite::Error::StaleData)
+        }
+    } else {
+        quote! {
+            q.fetch_one(&mut *conn).await.map_err(::ormlite::Error::from)
+        }
+    }
+}
+
+/// Update a modified joined struct, and replace it with the updated row.
+/// Assumed bindings:
+/// - `join`: `&mut Join<T>`, or `&mut Option<Join<T>>` for optional joins
+/// - `conn`: an open transaction
+fn update_join(c: &ColumnMeta, db: &TokenStream) -> TokenStream {
+    let update = quote! {
+        if let Some(modification) = join._take_modification() {
+            let updated = ::ormlite::model::Model::<#db>::update_all_fields_cascade(modification, &mut *conn).await?;
+            *join = ::ormlite::model::Join::_query_result(updated);
+        }
+    };
+    if c.is_option() {
+        quote! {
+            if let Some(join) = join {
+                #update
+            }
+        }
+    } else {
+        update
+    }
+}
@@ -24,6 +24,7 @@ use ormlite_attr::TableMeta;
 use ormlite_attr::schema_from_filepaths;
 use ormlite_core::config::get_var_model_folders;
 
+use crate::codegen::columns::struct_Columns;
 use crate::codegen::common::OrmliteCodegen;
 use crate::codegen::from_row::{impl_FromRow, impl_from_row_using_aliases};
 use crate::codegen::insert::impl_InsertModel;
@@ -134,7 +135,7 @@ pub fn expand_ormlite_model(input: TokenStream) -> TokenStream {
 
     let primary = {
         let db = first.as_ref();
-        let impl_TableMeta = impl_TableMeta(&meta.table, Some(meta.pkey.name.as_str()));
+        let impl_TableMeta = impl_TableMeta(&meta.table);
         let impl_JoinMeta = impl_JoinMeta(&meta);
         let static_join_descriptions = static_join_descriptions(&meta.table, &tables);
         let impl_Model = impl_Model(db, &meta, tables);
@@ -147,6 +148,8 @@ pub fn expand_ormlite_model(input: TokenStream) -> TokenStream {
         let struct_InsertModel = struct_InsertModel(&ast, &meta);
         let impl_InsertModel = impl_InsertModel(db, &meta);
 
+        let struct_Columns = struct_Columns(&ast, &meta);
+
         quote! {
             #impl_TableMeta
             #impl_JoinMeta
@@ -161,6 +164,8 @@ pub fn expand_ormlite_model(input: TokenStream) -> TokenStream {
 
             #struct_InsertModel
             #impl_InsertModel
+
+            #struct_Columns
         }
     };
 
@@ -224,7 +229,7 @@ pub fn expand_derive_table_meta(input: TokenStream) -> TokenStream {
 
     let table_meta = TableMeta::from_derive(&ast);
     let databases = get_databases(&table_meta);
-    let impl_TableMeta = impl_TableMeta(&table_meta, table_meta.pkey.as_deref());
+    let impl_TableMeta = impl_TableMeta(&table_meta);
     TokenStream::from(impl_TableMeta)
 }
 
@@ -14,7 +14,10 @@ pub mod model;
 
 pub mod query_builder {
     pub use ormlite_core::insert::OnConflict;
-    pub use ormlite_core::query_builder::{Placeholder, QueryBuilderArgs, SelectQueryBuilder};
+    pub use ormlite_core::query_builder::{
+        Condition, Cursor, DeleteQueryBuilder, IntoCondition, Page, Placeholder, ProjectionQueryBuilder, QueryBuilderArgs,
+        RowsAffected, SelectQueryBuilder, TypedColumn, UpdateQueryBuilder,
+    };
 }
 
 pub mod types {
@@ -39,6 +42,7 @@ pub mod __private {
     pub use ormlite_core::join::JoinDescription;
     pub use sql::query::{Values, Value};
     pub use sql::{Insert, Dialect, ToSql};
+    pub use ormlite_core::validate;
 }
 
 #[cfg(feature = "postgres")]
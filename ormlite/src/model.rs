@@ -1,4 +1,4 @@
-pub use ormlite_core::join::{Join, JoinMeta, Loadable};
+pub use ormlite_core::join::{Join, JoinMeta, LoadAll, Loadable};
 pub use ormlite_core::model::*;
 pub use ormlite_macro::{FromRow, Insert, IntoArguments, Model, TableMeta};
 pub use sqlx::IntoArguments;
@@ -9,6 +9,12 @@ fn test_postgres_complex() {
     set_path_and_run("tests/postgres/complex.rs");
 }
 
+/// Needs DATABASE_URL to point to a Postgres database.
+#[test]
+fn test_postgres_row_lock() {
+    set_path_and_run("tests/postgres/row_lock.rs");
+}
+
 // #[test]
 // fn test_postgres_join() {
 //     set_path_and_run("tests/postgres/join.rs");
@@ -0,0 +1,106 @@
+use ormlite::model::*;
+use ormlite::{query, Connection};
+use sql::{Dialect, ToSql};
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Queue {
+    id: i32,
+    name: String,
+}
+
+#[derive(Model, Debug)]
+pub struct Job {
+    id: i32,
+    name: String,
+    #[ormlite(column = "queue_id")]
+    queue: Join<Queue>,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let url = std::env::var("DATABASE_URL").unwrap();
+    let mut conn = ormlite::postgres::PgConnection::connect(&url).await.unwrap();
+    query("drop table if exists job, queue").execute(&mut conn).await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(Dialect::Postgres);
+        query(&sql).execute(&mut conn).await.unwrap();
+    }
+
+    // The lock comes last, after LIMIT.
+    let sql = Job::select()
+        .join(Job::queue())
+        .order_asc("id")
+        .limit(1)
+        .for_update()
+        .skip_locked()
+        .to_sql()
+        .unwrap();
+    assert_eq!(
+        sql,
+        r#"SELECT "job".*, "queue"."id" AS "__queue__id", "queue"."name" AS "__queue__name" FROM "job" LEFT JOIN "queue" AS "queue" ON "job"."queue_id" = "queue"."id" ORDER BY id ASC LIMIT 1 FOR UPDATE SKIP LOCKED"#
+    );
+
+    Queue::builder().id(1).name("default").insert(&mut conn).await.unwrap();
+    for (id, name) in [(1, "first"), (2, "second")] {
+        Job {
+            id,
+            name: name.to_string(),
+            queue: Join::new_with_id(1),
+        }
+        .insert(&mut conn)
+        .await
+        .unwrap();
+    }
+
+    // Two workers each claim the next job. The second skips the row the first holds a lock on.
+    let mut other = ormlite::postgres::PgConnection::connect(&url).await.unwrap();
+    let mut first = conn.begin().await.unwrap();
+    let mut second = other.begin().await.unwrap();
+    let claimed = Job::select()
+        .order_asc("id")
+        .limit(1)
+        .for_update()
+        .skip_locked()
+        .fetch_one(&mut *first)
+        .await
+        .unwrap();
+    assert_eq!(claimed.id, 1);
+    let claimed = Job::select()
+        .order_asc("id")
+        .limit(1)
+        .for_update()
+        .skip_locked()
+        .fetch_one(&mut *second)
+        .await
+        .unwrap();
+    assert_eq!(claimed.id, 2);
+
+    // Without SKIP LOCKED, NOWAIT fails instead of waiting for the lock.
+    let locked = Job::select()
+        .where_bind("id = ?", 1)
+        .for_share()
+        .nowait()
+        .fetch_one(&mut *second)
+        .await;
+    assert!(locked.is_err());
+    second.rollback().await.unwrap();
+    first.commit().await.unwrap();
+
+    // Once the lock is released, the row can be claimed again.
+    let mut second = other.begin().await.unwrap();
+    let claimed = Job::select()
+        .order_asc("id")
+        .limit(1)
+        .for_update()
+        .skip_locked()
+        .fetch_one(&mut *second)
+        .await
+        .unwrap();
+    assert_eq!(claimed.id, 1);
+    second.commit().await.unwrap();
+}
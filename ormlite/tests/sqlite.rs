@@ -12,8 +12,31 @@ fn test_sqlite() {
     set_path_and_run("tests/sqlite/04-allow-clone-primary-key.rs");
     set_path_and_run("tests/sqlite/05-keyword-column.rs");
     set_path_and_run("tests/sqlite/06-insert.rs");
-    // t.pass("tests/03-many-to-many.rs");
-    // t.pass("tests/04-one-to-many.rs");
+    set_path_and_run("tests/sqlite/07-one-to-many-join.rs");
+    set_path_and_run("tests/sqlite/08-many-to-many-join.rs");
+    set_path_and_run("tests/sqlite/09-load-all.rs");
+    set_path_and_run("tests/sqlite/10-nested-join.rs");
+    set_path_and_run("tests/sqlite/11-optional-join.rs");
+    set_path_and_run("tests/sqlite/12-join-type.rs");
+    set_path_and_run("tests/sqlite/13-cascade-update.rs");
+    set_path_and_run("tests/sqlite/14-composite-primary-key.rs");
+    set_path_and_run("tests/sqlite/15-typed-columns.rs");
+    set_path_and_run("tests/sqlite/16-where-in.rs");
+    set_path_and_run("tests/sqlite/17-fetch-stream.rs");
+    set_path_and_run("tests/sqlite/18-aggregates.rs");
+    set_path_and_run("tests/sqlite/19-projection.rs");
+    set_path_and_run("tests/sqlite/20-pagination.rs");
+    set_path_and_run("tests/sqlite/21-bulk-update.rs");
+    set_path_and_run("tests/sqlite/22-bulk-delete.rs");
+    set_path_and_run("tests/sqlite/23-row-lock.rs");
+    set_path_and_run("tests/sqlite/24-subquery.rs");
+    set_path_and_run("tests/sqlite/25-union.rs");
+    set_path_and_run("tests/sqlite/26-to-sql.rs");
+    set_path_and_run("tests/sqlite/27-timestamps.rs");
+    set_path_and_run("tests/sqlite/28-soft-delete.rs");
+    set_path_and_run("tests/sqlite/29-version.rs");
+    set_path_and_run("tests/sqlite/30-hooks.rs");
+    set_path_and_run("tests/sqlite/31-validate.rs");
 }
 
 #[test]
@@ -17,10 +17,29 @@ pub struct Person2 {
     age: u8,
 }
 
+/// Written by hand, so it gets the default `primary_key_columns`.
+pub struct Tag;
+
+impl TableMeta for Tag {
+    fn table_name() -> &'static str {
+        "tag"
+    }
+
+    fn table_columns() -> &'static [&'static str] {
+        &["name", "id"]
+    }
+
+    fn primary_key() -> Option<&'static str> {
+        Some("id")
+    }
+}
+
 fn main() {
     assert_eq!(Person::table_name(), "person");
     assert_eq!(Person::table_columns(), &["id", "name", "age"]);
     assert_eq!(Person::primary_key(), Some("id"));
 
     assert_eq!(Person2::table_columns(), &["id", "old"]);
+
+    assert_eq!(Tag::primary_key_columns(), &["id"]);
 }
@@ -0,0 +1,125 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Author {
+    id: i32,
+    name: String,
+    #[ormlite(foreign_field = Post::author_id)]
+    posts: Join<Vec<Post>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Post {
+    id: i32,
+    author_id: i32,
+    title: String,
+    #[ormlite(foreign_field = Comment::post)]
+    comments: Join<Vec<Comment>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Comment {
+    id: i32,
+    #[ormlite(column = "post_id")]
+    post: Join<Post>,
+    body: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        assert!(!sql.contains("posts"), "one to many joins don't have a column");
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, name) in [(1, "Alice"), (2, "Bob"), (3, "Carol")] {
+        Author::builder().id(id).name(name).insert(&mut db).await.unwrap();
+    }
+    for (id, author_id, title) in [(1, 1, "first"), (2, 1, "second"), (3, 2, "third")] {
+        Post {
+            id,
+            author_id,
+            title: title.to_string(),
+            comments: Join::default(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let authors = Author::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    assert!(!authors[0].posts.loaded(), "joins aren't loaded unless requested");
+
+    let authors = Author::select()
+        .join(Author::posts())
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(authors.len(), 3);
+    let titles = authors[0].posts.iter().map(|p| p.title.as_str()).collect::<Vec<_>>();
+    assert_eq!(titles, vec!["first", "second"]);
+    assert_eq!(authors[1].posts.len(), 1);
+    assert!(authors[2].posts.loaded());
+    assert!(authors[2].posts.is_empty());
+
+    let bob = Author::select()
+        .join(Author::posts())
+        .where_bind("id = ?", 2)
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(bob.posts[0].title, "third");
+
+    for (id, body) in [(1, "nice"), (2, "great")] {
+        Comment {
+            id,
+            post: Join::new_with_id(3),
+            body: body.to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    let post = Post::select()
+        .join(Post::comments())
+        .where_bind("id = ?", 3)
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(post.comments.len(), 2);
+    assert_eq!(post.comments[1].body, "great");
+
+    let nobody = Author::select()
+        .join(Author::posts())
+        .where_bind("id = ?", 4)
+        .fetch_optional(&mut db)
+        .await
+        .unwrap();
+    assert!(nobody.is_none());
+
+    // Only many to one joins can be inner joined or filtered. Other joins fail when the query is built.
+    let query = Author::select().inner_join(Author::posts());
+    assert!(query.to_sql().is_err());
+    assert!(query.fetch_all(&mut db).await.is_err());
+    let query = Author::select().join_on(Author::posts(), "posts.title = 'first'");
+    assert!(query.fetch_all(&mut db).await.is_err());
+    let query = Author::select().join_on_bind(Author::posts(), "posts.id = ?", 1);
+    assert!(query.into_query_and_args().is_err());
+
+    // Chains of joins fail the same way if a join isn't many to one.
+    let query = Comment::select().join(Comment::post().then(Post::comments()));
+    assert!(query.to_sql().is_err());
+    assert!(query.fetch_all(&mut db).await.is_err());
+    let query = Author::select().join(Author::posts().then(Post::comments()));
+    assert!(query.fetch_all(&mut db).await.is_err());
+}
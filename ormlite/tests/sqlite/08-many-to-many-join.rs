@@ -0,0 +1,110 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use ormlite_core::schema::statement_to_sql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(join_table = "user_role")]
+    roles: Join<Vec<Role>>,
+    #[ormlite(join_table = "follow", local_key = "follower_id", foreign_key = "followee_id")]
+    following: Join<Vec<User>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Role {
+    id: i32,
+    name: String,
+    #[ormlite(join_table = "user_role")]
+    users: Join<Vec<User>>,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    let mut created = Vec::new();
+    for s in migration.statements {
+        let sql = statement_to_sql(&s, sql::Dialect::Sqlite);
+        if sql.contains("\"user_role\" (") {
+            assert!(sql.contains("\"user_id\""), "join table columns are inferred from the primary key");
+            assert!(sql.contains("\"role_id\""), "join table columns are inferred from the primary key");
+            assert!(sql.contains("PRIMARY KEY (\"user_id\", \"role_id\")"));
+        }
+        if sql.contains("\"follow\" (") {
+            assert!(sql.contains("\"follower_id\""));
+            assert!(sql.contains("\"followee_id\""));
+        }
+        created.push(sql.clone());
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+    assert_eq!(
+        created.iter().filter(|s| s.contains("\"user_role\" (")).count(),
+        1,
+        "both sides of the join share one join table"
+    );
+
+    for (id, name) in [(1, "Alice"), (2, "Bob"), (3, "Carol")] {
+        User::builder().id(id).name(name).insert(&mut db).await.unwrap();
+    }
+    for (id, name) in [(1, "admin"), (2, "editor")] {
+        Role::builder().id(id).name(name).insert(&mut db).await.unwrap();
+    }
+    // Attaching twice relates the models once.
+    for (user, role) in [(1, 1), (1, 2), (2, 2), (1, 2)] {
+        User::roles().attach(user, role, &mut db).await.unwrap();
+    }
+    User::following().attach(1, 2, &mut db).await.unwrap();
+    User::following().attach(3, 2, &mut db).await.unwrap();
+
+    let users = User::select()
+        .join(User::roles())
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    let mut names = users[0].roles.iter().map(|r| r.name.as_str()).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["admin", "editor"]);
+    assert_eq!(users[1].roles.len(), 1);
+    assert_eq!(users[1].roles[0].name, "editor");
+    assert!(users[2].roles.loaded());
+    assert!(users[2].roles.is_empty());
+    assert!(!users[0].following.loaded());
+
+    let editor = Role::select()
+        .join(Role::users())
+        .where_bind("id = ?", 2)
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    let mut users = editor.users.iter().map(|u| u.name.as_str()).collect::<Vec<_>>();
+    users.sort();
+    assert_eq!(users, vec!["Alice", "Bob"]);
+
+    let alice = User::select()
+        .join(User::following())
+        .where_bind("id = ?", 1)
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(alice.following.len(), 1);
+    assert_eq!(alice.following[0].name, "Bob");
+
+    User::roles().detach(1, 2, &mut db).await.unwrap();
+    let alice = User::select()
+        .join(User::roles())
+        .join(User::following())
+        .where_bind("id = ?", 1)
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(alice.roles.len(), 1);
+    assert_eq!(alice.roles[0].name, "admin");
+    assert_eq!(alice.following.len(), 1);
+}
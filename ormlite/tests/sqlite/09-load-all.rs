@@ -0,0 +1,92 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+    #[ormlite(foreign_field = User::organization)]
+    members: Join<Vec<User>>,
+}
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(column = "organization_id")]
+    organization: Join<Organization>,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, name) in [(1, "Acme"), (2, "Globex")] {
+        Organization::builder().id(id).name(name).insert(&mut db).await.unwrap();
+    }
+    for (id, name, org) in [(1, "Alice", 1), (2, "Bob", 1), (3, "Carol", 2)] {
+        User {
+            id,
+            name: name.to_string(),
+            organization: Join::new_with_id(org),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let mut users = User::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    users.push(User {
+        id: 4,
+        name: "Dan".to_string(),
+        organization: Join::new_with_id(3),
+    });
+    assert!(users.iter().all(|u| !u.organization.loaded()));
+    users.load_all(User::organization(), &mut db).await.unwrap();
+    assert_eq!(users[0].organization.name, "Acme");
+    assert_eq!(users[1].organization.name, "Acme", "models sharing a relation each get it");
+    assert_eq!(users[2].organization.name, "Globex");
+    assert!(!users[3].organization.loaded(), "a missing relation is left unloaded");
+
+    let mut orgs = Organization::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    orgs.as_mut_slice()
+        .load_all(Organization::members(), &mut db)
+        .await
+        .unwrap();
+    assert_eq!(orgs[0].members.len(), 2);
+    assert_eq!(orgs[1].members[0].name, "Carol");
+
+    let mut none: Vec<User> = Vec::new();
+    none.load_all(User::organization(), &mut db).await.unwrap();
+
+    // Batches larger than the database's limit on bound parameters are loaded with several queries.
+    ormlite::query(
+        "WITH RECURSIVE n(id) AS (SELECT 3 UNION ALL SELECT id + 1 FROM n WHERE id < 40002) \
+         INSERT INTO organization (id, name) SELECT id, 'org' FROM n",
+    )
+    .execute(&mut db)
+    .await
+    .unwrap();
+    ormlite::query(
+        "INSERT INTO user (id, name, organization_id) SELECT id + 2, 'member', id FROM organization WHERE id > 2",
+    )
+    .execute(&mut db)
+    .await
+    .unwrap();
+    let mut users = User::select().fetch_all(&mut db).await.unwrap();
+    users.load_all(User::organization(), &mut db).await.unwrap();
+    assert!(users.iter().all(|u| u.organization.loaded()));
+    let mut orgs = Organization::select().fetch_all(&mut db).await.unwrap();
+    orgs.load_all(Organization::members(), &mut db).await.unwrap();
+    assert_eq!(orgs.iter().map(|o| o.members.len()).sum::<usize>(), users.len());
+}
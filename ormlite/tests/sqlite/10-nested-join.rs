@@ -0,0 +1,102 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(column = "organization_id")]
+    organization: Join<Organization>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+    #[ormlite(column = "owner_id")]
+    owner: Join<Owner>,
+}
+
+#[derive(Model, Debug)]
+pub struct Owner {
+    id: i32,
+    name: String,
+    #[ormlite(column = "address_id")]
+    address: Join<Address>,
+}
+
+#[derive(Model, Debug)]
+pub struct Address {
+    id: i32,
+    city: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    Address::builder().id(1).city("Paris".to_string()).insert(&mut db).await.unwrap();
+    Owner {
+        id: 1,
+        name: "Olivia".to_string(),
+        address: Join::new_with_id(1),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    Organization {
+        id: 1,
+        name: "Acme".to_string(),
+        owner: Join::new_with_id(1),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    User {
+        id: 1,
+        name: "Alice".to_string(),
+        organization: Join::new_with_id(1),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+
+    let user = User::select()
+        .join(User::organization().then(Organization::owner()))
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(user.organization.name, "Acme");
+    assert_eq!(user.organization.owner.name, "Olivia");
+    assert!(!user.organization.owner.address.loaded());
+    assert_eq!(user.organization.owner.address.id, 1);
+
+    let user = User::select()
+        .join(User::organization())
+        .join(
+            User::organization()
+                .then(Organization::owner())
+                .then(Owner::address()),
+        )
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(user.organization.owner.address.city, "Paris");
+
+    let query = User::select()
+        .join(User::organization().then(Organization::owner()))
+        .into_query_and_args()
+        .unwrap()
+        .0;
+    assert!(query.contains(r#"LEFT JOIN "owner" AS "organization__owner""#), "{query}");
+}
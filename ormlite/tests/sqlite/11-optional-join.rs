@@ -0,0 +1,109 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(column = "organization_id")]
+    organization: Option<Join<Organization>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+    #[ormlite(foreign_field = User::organization)]
+    members: Join<Vec<User>>,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        if sql.contains("\"user\" (") {
+            let column = sql.lines().find(|l| l.contains("\"organization_id\"")).unwrap();
+            assert!(!column.contains("NOT NULL"), "optional joins have a nullable column: {column}");
+        }
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    let alice = User {
+        id: 1,
+        name: "Alice".to_string(),
+        organization: Some(Join::new(Organization {
+            id: 1,
+            name: "Acme".to_string(),
+            members: Join::default(),
+        })),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert_eq!(alice.organization.as_ref().unwrap().name, "Acme");
+
+    let bob = User {
+        id: 2,
+        name: "Bob".to_string(),
+        organization: None,
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert!(bob.organization.is_none());
+    User::builder()
+        .id(3)
+        .name("Carol".to_string())
+        .organization(Some(Join::new_with_id(1)))
+        .insert(&mut db)
+        .await
+        .unwrap();
+
+    let users = User::select()
+        .join(User::organization())
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users[0].organization.as_ref().unwrap().name, "Acme");
+    assert!(users[1].organization.is_none(), "a NULL foreign key decodes to None");
+    assert_eq!(users[2].organization.as_ref().unwrap().name, "Acme");
+
+    let mut users = User::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    assert!(!users[0].organization.as_ref().unwrap().loaded());
+    users.load_all(User::organization(), &mut db).await.unwrap();
+    assert!(users[0].organization.as_ref().unwrap().loaded());
+    assert!(users[1].organization.is_none());
+
+    let org = Organization::select()
+        .join(Organization::members())
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(org.members.len(), 2);
+
+    let mut alice = users.remove(0);
+    alice.organization = None;
+    let alice = alice.update_all_fields(&mut db).await.unwrap();
+    assert!(alice.organization.is_none());
+    let alice = User::fetch_one(1, &mut db).await.unwrap();
+    assert!(alice.organization.is_none(), "None is written as NULL on update");
+
+    let carol = users.remove(1);
+    let carol = carol.update_partial().organization(None).update(&mut db).await.unwrap();
+    assert!(carol.organization.is_none());
+    let alice = alice
+        .update_partial()
+        .organization(Some(Join::new_with_id(1)))
+        .update(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(alice.organization.unwrap().id, 1);
+}
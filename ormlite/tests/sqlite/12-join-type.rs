@@ -0,0 +1,118 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(column = "organization_id")]
+    organization: Option<Join<Organization>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+    archived: bool,
+    #[ormlite(column = "owner_id")]
+    owner: Join<Owner>,
+}
+
+#[derive(Model, Debug)]
+pub struct Owner {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    Owner::builder().id(1).name("Olivia".to_string()).insert(&mut db).await.unwrap();
+    for (id, name, archived) in [(1, "Acme", false), (2, "Globex", true)] {
+        Organization {
+            id,
+            name: name.to_string(),
+            archived,
+            owner: Join::new_with_id(1),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    for (id, name, org) in [(1, "Alice", Some(1)), (2, "Bob", Some(2)), (3, "Carol", None)] {
+        User {
+            id,
+            name: name.to_string(),
+            organization: org.map(Join::new_with_id),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let users = User::select()
+        .inner_join(User::organization())
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 2, "users without an organization are filtered out");
+    assert_eq!(users[1].organization.as_ref().unwrap().name, "Globex");
+
+    let users = User::select()
+        .join_on(User::organization(), "organization.archived = false")
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 3);
+    assert_eq!(users[0].organization.as_ref().unwrap().name, "Acme");
+    assert!(
+        !users[1].organization.as_ref().unwrap().loaded(),
+        "a left join doesn't load a relation failing the condition"
+    );
+
+    let users = User::select()
+        .where_bind("\"user\".name != ?", "Nobody")
+        .inner_join(User::organization())
+        .join_on_bind(User::organization(), "organization.name = ?", "Globex")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 1, "join arguments are bound before where arguments");
+    assert_eq!(users[0].name, "Bob");
+
+    let user = User::select()
+        .inner_join(User::organization().then(Organization::owner()))
+        .join_on_bind(
+            User::organization().then(Organization::owner()),
+            "organization__owner.name = ?",
+            "Olivia",
+        )
+        .join_on(User::organization(), "organization.archived = false")
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(user.name, "Alice");
+    assert_eq!(user.organization.unwrap().owner.name, "Olivia");
+
+    let query = User::select()
+        .join(User::organization())
+        .inner_join(User::organization())
+        .into_query_and_args()
+        .unwrap()
+        .0;
+    assert!(query.contains(r#" JOIN "organization" AS "organization""#), "{query}");
+    assert!(!query.contains("LEFT JOIN"), "{query}");
+}
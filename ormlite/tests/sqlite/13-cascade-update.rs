@@ -0,0 +1,118 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(column = "organization_id")]
+    organization: Join<Organization>,
+    #[ormlite(column = "team_id")]
+    team: Option<Join<Team>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+    #[ormlite(column = "owner_id")]
+    owner: Join<Owner>,
+}
+
+#[derive(Model, Debug)]
+pub struct Owner {
+    id: i32,
+    name: String,
+}
+
+#[derive(Model, Debug)]
+pub struct Team {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    User {
+        id: 1,
+        name: "Alice".to_string(),
+        organization: Join::new(Organization {
+            id: 1,
+            name: "Acme".to_string(),
+            owner: Join::new(Owner {
+                id: 1,
+                name: "Olivia".to_string(),
+            }),
+        }),
+        team: Some(Join::new(Team {
+            id: 1,
+            name: "Red".to_string(),
+        })),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+
+    let fetch = || {
+        User::select()
+            .join(User::organization().then(Organization::owner()))
+            .join(User::team())
+    };
+
+    let mut user = fetch().fetch_one(&mut db).await.unwrap();
+    user.organization.name = "Ignored".to_string();
+    user.update_all_fields(&mut db).await.unwrap();
+    let mut user = fetch().fetch_one(&mut db).await.unwrap();
+    assert_eq!(user.organization.name, "Acme", "update_all_fields doesn't cascade");
+
+    user.name = "Alicia".to_string();
+    user.organization.name = "Acme Corp".to_string();
+    user.organization.owner.name = "Olive".to_string();
+    user.team.as_mut().unwrap().name = "Blue".to_string();
+    let user = user.update_all_fields_cascade(&mut db).await.unwrap();
+    assert_eq!(user.organization.name, "Acme Corp", "the updated join is returned");
+    assert_eq!(user.team.as_ref().unwrap().name, "Blue");
+    let user = fetch().fetch_one(&mut db).await.unwrap();
+    assert_eq!(user.name, "Alicia");
+    assert_eq!(user.organization.name, "Acme Corp");
+    assert_eq!(user.organization.owner.name, "Olive", "updates cascade through nested joins");
+    assert_eq!(user.team.as_ref().unwrap().name, "Blue");
+
+    let mut organization = Join::new(Organization::fetch_one(1, &mut db).await.unwrap());
+    organization.name = "Acme Inc".to_string();
+    let updated = user
+        .update_partial()
+        .name("Al".to_string())
+        .organization(organization)
+        .update_cascade(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(updated.organization.name, "Acme Inc");
+    let user = fetch().fetch_one(&mut db).await.unwrap();
+    assert_eq!(user.name, "Al");
+    assert_eq!(user.organization.name, "Acme Inc");
+
+    let mut user = user;
+    user.name = "Rolled back".to_string();
+    user.organization.name = "Rolled back".to_string();
+    user.team = Some(Join::new(Team {
+        id: 2,
+        name: "Missing".to_string(),
+    }));
+    assert!(user.update_all_fields_cascade(&mut db).await.is_err());
+    let user = fetch().fetch_one(&mut db).await.unwrap();
+    assert_eq!(user.name, "Al", "a failed cascade rolls back the whole update");
+    assert_eq!(user.organization.name, "Acme Inc");
+}
@@ -0,0 +1,93 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use ormlite_core::schema::statement_to_sql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+#[derive(Model, Debug)]
+pub struct Role {
+    id: i32,
+    name: String,
+}
+
+#[derive(Model, Debug)]
+pub struct UserRole {
+    #[ormlite(primary_key)]
+    user_id: i32,
+    #[ormlite(primary_key)]
+    role_id: i32,
+    granted_by: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = statement_to_sql(&s, sql::Dialect::Sqlite);
+        if sql.contains("\"user_role\" (") {
+            assert!(sql.contains(r#"PRIMARY KEY ("user_id", "role_id")"#), "{sql}");
+            assert!(sql.contains(r#""user_id" integer NOT NULL REFERENCES "user"("id")"#), "{sql}");
+        }
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    assert_eq!(UserRole::primary_key_columns(), ["user_id", "role_id"]);
+    assert_eq!(UserRole::primary_key(), None);
+    assert_eq!(User::primary_key_columns(), ["id"]);
+
+    User::builder().id(1).name("Alice").insert(&mut db).await.unwrap();
+    for (id, name) in [(1, "admin"), (2, "editor")] {
+        Role::builder().id(id).name(name).insert(&mut db).await.unwrap();
+    }
+    for role_id in [1, 2] {
+        UserRole {
+            user_id: 1,
+            role_id,
+            granted_by: "root".to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    let duplicate = UserRole {
+        user_id: 1,
+        role_id: 2,
+        granted_by: "root".to_string(),
+    }
+    .insert(&mut db)
+    .await;
+    assert!(duplicate.is_err(), "the composite primary key is unique");
+
+    let mut user_role = UserRole::fetch_one((1, 2), &mut db).await.unwrap();
+    assert_eq!(user_role.role_id, 2);
+    assert_eq!(user_role._id(), (1, 2));
+
+    user_role.granted_by = "alice".to_string();
+    let user_role = user_role.update_all_fields(&mut db).await.unwrap();
+    assert_eq!(user_role.granted_by, "alice");
+    let admin = UserRole::fetch_one((1, 1), &mut db).await.unwrap();
+    assert_eq!(admin.granted_by, "root", "only the matching row is updated");
+
+    let user_role = user_role
+        .update_partial()
+        .granted_by("bob".to_string())
+        .update(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(user_role.granted_by, "bob");
+
+    user_role.delete(&mut db).await.unwrap();
+    assert!(UserRole::fetch_one((1, 2), &mut db).await.is_err());
+    let remaining = UserRole::select().fetch_all(&mut db).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].role_id, 1);
+}
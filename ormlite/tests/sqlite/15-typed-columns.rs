@@ -0,0 +1,99 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    age: i32,
+    nickname: Option<String>,
+    #[ormlite(column = "organization_id")]
+    organization: Option<Join<Organization>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    Organization {
+        id: 1,
+        name: "Acme".to_string(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    for (id, name, age, nickname, org) in [
+        (1, "Alice", 30, Some("Al"), Some(1)),
+        (2, "Bob", 17, None, None),
+        (3, "Anna", 45, None, Some(1)),
+    ] {
+        User {
+            id,
+            name: name.to_string(),
+            age,
+            nickname: nickname.map(str::to_string),
+            organization: org.map(Join::new_with_id),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let c = User::columns();
+    assert_eq!(c.age.name, "age");
+    assert_eq!(c.organization.name, "organization_id");
+
+    let users = User::select().where_(c.age.gt(18)).order_asc("id").fetch_all(&mut db).await.unwrap();
+    assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), vec![1, 3]);
+
+    let user = User::select().where_(c.name.eq("Bob")).fetch_one(&mut db).await.unwrap();
+    assert_eq!(user.id, 2);
+
+    let users = User::select().where_(c.id.in_(vec![1, 2])).order_asc("id").fetch_all(&mut db).await.unwrap();
+    assert_eq!(users.len(), 2);
+
+    let users = User::select().where_(c.id.in_(Vec::<i32>::new())).fetch_all(&mut db).await.unwrap();
+    assert!(users.is_empty());
+
+    let users = User::select()
+        .where_(c.name.like("A%").and(c.nickname.is_null()))
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), vec![3]);
+
+    let user = User::select().where_(c.nickname.eq("Al")).fetch_one(&mut db).await.unwrap();
+    assert_eq!(user.id, 1);
+
+    let users = User::select().where_(c.organization.eq(1)).fetch_all(&mut db).await.unwrap();
+    assert_eq!(users.len(), 2);
+
+    // typed conditions and raw clauses bind in order
+    let users = User::select()
+        .where_("name <> ?")
+        .bind("Anna")
+        .where_(c.age.lte(30).or(c.age.gte(40)))
+        .where_("age < ?")
+        .bind(100)
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), vec![1, 2]);
+}
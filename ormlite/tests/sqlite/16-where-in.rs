@@ -0,0 +1,73 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, name) in [(1, "Alice"), (2, "Bob"), (3, "Carol"), (4, "Dave")] {
+        User {
+            id,
+            name: name.to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let users = User::select()
+        .where_in("id", vec![1, 3, 4])
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), vec![1, 3, 4]);
+
+    let users = User::select().where_in("id", Vec::<i32>::new()).fetch_all(&mut db).await.unwrap();
+    assert!(users.is_empty());
+    let users = User::select()
+        .where_("id NOT IN (?)")
+        .bind_list(Vec::<i32>::new())
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 4, "an empty list excludes no rows");
+
+    // a list binds to a single placeholder, in order with the other arguments
+    let names = ["Alice", "Bob", "Dave"];
+    let users = User::select()
+        .where_("id > ?")
+        .bind(1)
+        .where_("name IN (?)")
+        .bind_list(names)
+        .where_("id < ?")
+        .bind(4)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), vec![2]);
+
+    let (sql, args) = User::select()
+        .where_in("id", [1, 2])
+        .where_bind("name = ?", "Bob")
+        .into_query_and_args()
+        .unwrap();
+    assert!(sql.ends_with("WHERE id IN (?, ?) AND name = ?"), "{sql}");
+    assert_eq!(args.len(), 3);
+}
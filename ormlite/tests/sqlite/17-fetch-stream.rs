@@ -0,0 +1,76 @@
+use futures::TryStreamExt;
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    #[ormlite(column = "organization_id")]
+    organization: Option<Join<Organization>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+    #[ormlite(foreign_field = User::organization)]
+    users: Join<Vec<User>>,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    Organization::builder()
+        .id(1)
+        .name("Acme".to_string())
+        .insert(&mut db)
+        .await
+        .unwrap();
+    for id in 1..=100 {
+        User {
+            id,
+            name: format!("user{id}"),
+            organization: (id % 2 == 0).then(|| Join::new_with_id(1)),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let mut stream = User::select().where_bind("id > ?", 10).order_asc("id").fetch(&mut db);
+    let mut ids = Vec::new();
+    while let Some(user) = stream.try_next().await.unwrap() {
+        ids.push(user.id);
+    }
+    drop(stream);
+    assert_eq!(ids, (11..=100).collect::<Vec<_>>());
+
+    // many to one joins are part of the query, so they stream
+    let users: Vec<User> = User::select()
+        .join(User::organization())
+        .where_in("user.id", [1, 2])
+        .order_asc("user.id")
+        .fetch(&mut db)
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(users[0].organization.is_none());
+    assert_eq!(users[1].organization.as_ref().unwrap().name, "Acme");
+
+    // joins loaded with a separate query can't be streamed
+    let result: ormlite::Result<Vec<Organization>> =
+        Organization::select().join(Organization::users()).fetch(&mut db).try_collect().await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,107 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    age: i32,
+    #[ormlite(column = "organization_id")]
+    organization: Option<Join<Organization>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, name) in [(1, "Acme"), (2, "Globex")] {
+        Organization {
+            id,
+            name: name.to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    for (id, age, org) in [(1, 20, Some(1)), (2, 30, Some(1)), (3, 40, Some(2)), (4, 50, None)] {
+        User {
+            id,
+            name: format!("user{id}"),
+            age,
+            organization: org.map(Join::new_with_id),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(User::select().count(&mut db).await.unwrap(), 4);
+    let count = User::select()
+        .where_bind("age > ?", 25)
+        .order_asc("id")
+        .limit(1)
+        .offset(1)
+        .count(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+
+    // filters on joins are kept
+    let count = User::select()
+        .join_on_bind(User::organization(), "organization.name = ?", "Acme")
+        .where_("organization.id IS NOT NULL")
+        .count(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    // grouped queries count groups
+    let count = User::select()
+        .group_by("organization_id")
+        .count(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+    let count = User::select()
+        .select("COUNT(*) AS members")
+        .group_by("organization_id")
+        .having("members > 1")
+        .count(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(count, 1, "HAVING can refer to the selected columns");
+
+    assert!(User::select().where_bind("age = ?", 40).exists(&mut db).await.unwrap());
+    assert!(!User::select().where_bind("age = ?", 41).exists(&mut db).await.unwrap());
+
+    let max_age: i32 = User::select().aggregate("MAX(age)", &mut db).await.unwrap();
+    assert_eq!(max_age, 50);
+    let sum: i64 = User::select()
+        .where_bind("organization_id = ?", 1)
+        .aggregate("SUM(age)", &mut db)
+        .await
+        .unwrap();
+    assert_eq!(sum, 50);
+    let none: Option<i32> = User::select()
+        .where_bind("age > ?", 100)
+        .aggregate("MAX(age)", &mut db)
+        .await
+        .unwrap();
+    assert_eq!(none, None);
+}
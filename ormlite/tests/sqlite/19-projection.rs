@@ -0,0 +1,104 @@
+use futures::TryStreamExt;
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    email: String,
+    #[ormlite(column = "organization_id")]
+    organization: Option<Join<Organization>>,
+}
+
+#[derive(Model, Debug)]
+pub struct Organization {
+    id: i32,
+    name: String,
+}
+
+#[derive(FromRow, TableMeta, Debug)]
+pub struct UserName {
+    id: i32,
+    name: String,
+}
+
+#[derive(FromRow, Debug)]
+pub struct OrganizationSize {
+    organization_id: Option<i32>,
+    users: i64,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, name) in [(1, "Acme"), (2, "Globex")] {
+        Organization {
+            id,
+            name: name.to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    for (id, name, org) in [(1, "Alice", Some(1)), (2, "Bob", Some(1)), (3, "Carol", Some(2)), (4, "Dave", None)] {
+        User {
+            id,
+            name: name.to_string(),
+            email: format!("{}@example.com", name.to_lowercase()),
+            organization: org.map(Join::new_with_id),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let (sql, _) = User::select().select_as::<UserName>().into_query_and_args().unwrap();
+    assert_eq!(sql.trim_end(), r#"SELECT "user"."id", "user"."name" FROM "user""#);
+
+    // filters and joins are kept
+    let names = User::select()
+        .join_on_bind(User::organization(), "organization.name = ?", "Acme")
+        .where_("organization.id IS NOT NULL")
+        .order_desc("user.id")
+        .select_as::<UserName>()
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(names.iter().map(|u| u.name.as_str()).collect::<Vec<_>>(), vec!["Bob", "Alice"]);
+
+    let name = User::select()
+        .where_bind("id = ?", 3)
+        .select_as::<UserName>()
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(name.name, "Carol");
+
+    let sizes: Vec<OrganizationSize> = User::select()
+        .clear_select()
+        .select("organization_id")
+        .select("COUNT(*) AS users")
+        .group_by("organization_id")
+        .order_asc("organization_id")
+        .into_projection::<OrganizationSize>()
+        .fetch(&mut db)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        sizes.iter().map(|s| (s.organization_id, s.users)).collect::<Vec<_>>(),
+        vec![(None, 1), (Some(1), 2), (Some(2), 1)]
+    );
+}
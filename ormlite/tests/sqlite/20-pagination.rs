@@ -0,0 +1,140 @@
+use ormlite::model::*;
+use ormlite::query_builder::Cursor;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    age: i32,
+}
+
+#[derive(Model, Debug)]
+pub struct Reading {
+    id: i32,
+    value: f64,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for id in 1..=10 {
+        User {
+            id,
+            name: format!("user{id}"),
+            age: 20 + id % 3,
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let page = User::select()
+        .order_asc("id")
+        .paginate_after(None, 4, &mut db)
+        .await
+        .unwrap();
+    assert_eq!(page.items.iter().map(|u| u.id).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    let cursor = page.next_cursor.unwrap();
+    let page = User::select()
+        .order_asc("id")
+        .paginate_after(Some(&cursor), 4, &mut db)
+        .await
+        .unwrap();
+    assert_eq!(page.items.iter().map(|u| u.id).collect::<Vec<_>>(), vec![5, 6, 7, 8]);
+    let page = User::select()
+        .order_asc("id")
+        .paginate_after(page.next_cursor.as_ref(), 4, &mut db)
+        .await
+        .unwrap();
+    assert_eq!(page.items.iter().map(|u| u.id).collect::<Vec<_>>(), vec![9, 10]);
+    assert!(page.next_cursor.is_none());
+
+    // compound keys in mixed directions, with filters, and the cursor passed around as a string. 7 is on the second
+    // page's (age = ? AND id > ?) branch, so it's only left out if the filters apply to every branch.
+    let expected = User::select()
+        .where_bind("id <> ?", 5)
+        .where_bind("id <> ?", 7)
+        .order_desc("age")
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|u| u.id)
+        .collect::<Vec<_>>();
+    let mut ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let cursor_value = cursor.as_deref().map(|c| c.parse::<Cursor>().unwrap());
+        let page = User::select()
+            .where_bind("id <> ?", 5)
+            .where_bind("id <> ?", 7)
+            .order_desc("age")
+            .order_asc("id")
+            .paginate_after(cursor_value.as_ref(), 3, &mut db)
+            .await
+            .unwrap();
+        assert!(page.items.len() <= 3);
+        ids.extend(page.items.iter().map(|u| u.id));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+    assert_eq!(ids, expected);
+    assert_eq!(ids.len(), 8);
+    assert!(!ids.contains(&7));
+
+    // computed keys keep their type, so 10 sorts after 9
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = User::select()
+            .order_asc("id + 0")
+            .paginate_after(cursor.as_ref(), 3, &mut db)
+            .await
+            .unwrap();
+        ids.extend(page.items.iter().map(|u| u.id));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, (1..=10).collect::<Vec<_>>());
+
+    // reals that print the same at lower precision are told apart
+    for (id, value) in [(1, 0.1), (2, 0.3), (3, 0.1 + 0.2), (4, 0.7)] {
+        Reading { id, value }.insert(&mut db).await.unwrap();
+    }
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = Reading::select()
+            .order_asc("value")
+            .order_asc("id")
+            .paginate_after(cursor.as_ref(), 1, &mut db)
+            .await
+            .unwrap();
+        ids.extend(page.items.iter().map(|r| r.id));
+        cursor = page.next_cursor.map(|c| c.to_string().parse::<Cursor>().unwrap());
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+
+    let err = User::select().paginate_after(None, 3, &mut db).await;
+    assert!(err.is_err());
+}
@@ -0,0 +1,75 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    age: i32,
+    status: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = s.to_sql(sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, age) in [(1, 20), (2, 30), (3, 40), (4, 50)] {
+        User {
+            id,
+            name: format!("user{id}"),
+            age,
+            status: "active".to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    let updated = User::update_where()
+        .set("status", "senior")
+        .set_raw("age = age + 1")
+        .where_("age >= ?")
+        .bind(40)
+        .execute(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(updated, 2);
+    let users = User::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    assert_eq!(
+        users.iter().map(|u| (u.age, u.status.as_str())).collect::<Vec<_>>(),
+        vec![(20, "active"), (30, "active"), (41, "senior"), (51, "senior")]
+    );
+
+    // set arguments come before the WHERE arguments, whatever the call order
+    let users = User::update_where()
+        .where_bind("id IN (?, ?)", 1)
+        .bind(2)
+        .set("status", "junior")
+        .where_(User::columns().age.lt(25))
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, 1);
+    assert_eq!(users[0].status, "junior");
+
+    let (sql, args) = User::update_where().set("name", "x").into_query_and_args().unwrap();
+    assert_eq!(sql, r#"UPDATE "user" SET "name" = ?"#);
+    assert_eq!(args.len(), 1);
+
+    // Updates without a SET clause fail, and so do updates of unknown columns.
+    assert!(User::update_where().where_bind("id = ?", 1).execute(&mut db).await.is_err());
+    let query = User::update_where().set("nmae", "typo").where_bind("id = ?", 1);
+    assert!(query.to_sql().is_err());
+    assert!(query.execute(&mut db).await.is_err());
+}
@@ -0,0 +1,73 @@
+use ormlite::model::*;
+use ormlite::Connection;
+use sql::ToSql;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct User {
+    id: i32,
+    name: String,
+    organization_id: i32,
+}
+
+#[derive(Model, Debug)]
+#[ormlite(table = "membership")]
+pub struct Membership {
+    #[ormlite(primary_key)]
+    member_number: i32,
+    #[ormlite(primary_key)]
+    team_number: i32,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, org) in [(1, 1), (2, 1), (3, 2), (4, 2), (5, 3)] {
+        User {
+            id,
+            name: format!("user{id}"),
+            organization_id: org,
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(User::delete_by_id(5, &mut db).await.unwrap(), 1);
+    assert_eq!(User::delete_by_id(5, &mut db).await.unwrap(), 0);
+
+    let deleted = User::delete_where()
+        .where_bind("organization_id = ?", 1)
+        .execute(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 2);
+
+    let deleted = User::delete_where()
+        .where_(User::columns().id.eq(3))
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].name, "user3");
+
+    let remaining = User::select().fetch_all(&mut db).await.unwrap();
+    assert_eq!(remaining.iter().map(|u| u.id).collect::<Vec<_>>(), vec![4]);
+
+    for (member_number, team_number) in [(1, 1), (1, 2)] {
+        Membership { member_number, team_number }.insert(&mut db).await.unwrap();
+    }
+    assert_eq!(Membership::delete_by_id((1, 2), &mut db).await.unwrap(), 1);
+    assert_eq!(Membership::select().count(&mut db).await.unwrap(), 1);
+
+    assert_eq!(User::delete_where().execute(&mut db).await.unwrap(), 1);
+}
@@ -0,0 +1,39 @@
+use ormlite::model::*;
+use ormlite::Connection;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Job {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    Job {
+        id: 1,
+        name: "first".to_string(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+
+    // SQLite has no row locks, so asking for one is an error rather than a silently unlocked read.
+    assert!(Job::select().for_update().into_query_and_args().is_err());
+    assert!(Job::select().for_share().skip_locked().fetch_all(&mut db).await.is_err());
+    assert!(Job::select().nowait().into_query_and_args().is_err());
+
+    // Aggregates drop the lock, so they still work.
+    assert_eq!(Job::select().for_update().count(&mut db).await.unwrap(), 1);
+    assert_eq!(Job::select().fetch_all(&mut db).await.unwrap().len(), 1);
+}
@@ -0,0 +1,103 @@
+use ormlite::model::*;
+use ormlite::Connection;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Person {
+    id: i32,
+    name: String,
+}
+
+#[derive(Model, Debug)]
+pub struct Post {
+    id: i32,
+    author: i32,
+    score: i32,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, name) in [(1, "alice"), (2, "bob"), (3, "carol")] {
+        Person {
+            id,
+            name: name.to_string(),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    for (id, author, score) in [(1, 1, 10), (2, 1, 20), (3, 2, 5), (4, 3, 30)] {
+        Post { id, author, score }.insert(&mut db).await.unwrap();
+    }
+
+    // Arguments are placed in query order, regardless of the order they're bound in.
+    let authors = Post::select().clear_select().select("author").where_bind("score > ?", 8);
+    let people = Person::select()
+        .where_bind("name <> ?", "carol")
+        .where_in_subquery("id", authors)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(people.iter().map(|p| p.id).collect::<Vec<_>>(), vec![1]);
+
+    let top = Post::select().where_bind("score >= ?", 10);
+    let (sql, args) = Person::select()
+        .where_bind("name <> ?", "bob")
+        .with_query("top", top)
+        .dangerous_where("id IN (SELECT author FROM top)")
+        .into_query_and_args()
+        .unwrap();
+    assert_eq!(args.len(), 2);
+    assert!(sql.starts_with("WITH top AS (SELECT"));
+    let people = Person::select()
+        .where_bind("name <> ?", "bob")
+        .with_query("top", Post::select().where_bind("score >= ?", 10))
+        .dangerous_where("id IN (SELECT author FROM top)")
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(people.iter().map(|p| p.id).collect::<Vec<_>>(), vec![1, 3]);
+
+    let authors = Post::select()
+        .clear_select()
+        .select("author")
+        .where_bind("score < ?", 25)
+        .group_by("author")
+        .having_bind("COUNT(*) > ?", 1)
+        .into_projection::<(i32,)>()
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(authors, vec![(1,)]);
+
+    // Subqueries with lists expand their placeholders too.
+    let count = Person::select()
+        .where_in_subquery("id", Post::select().clear_select().select("author").where_in("id", vec![3, 4]))
+        .count(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    // A row lock in a subquery fails on SQLite like it does on the outer query.
+    let locked = Post::select().clear_select().select("author").for_update();
+    assert!(Person::select().where_in_subquery("id", locked).into_query_and_args().is_err());
+    let locked = Post::select().clear_select().select("author").for_update();
+    let projection = Person::select()
+        .clear_select()
+        .select("id")
+        .where_in_subquery("id", locked)
+        .into_projection::<(i32,)>();
+    assert!(projection.to_sql().is_err());
+    assert!(projection.fetch_all(&mut db).await.is_err());
+}
@@ -0,0 +1,67 @@
+use ormlite::model::*;
+use ormlite::Connection;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Document {
+    id: i32,
+    owner: i32,
+    shared_with: i32,
+}
+
+fn ids(documents: &[Document]) -> Vec<i32> {
+    documents.iter().map(|d| d.id).collect()
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, owner, shared_with) in [(1, 1, 2), (2, 2, 1), (3, 1, 1), (4, 3, 3), (5, 2, 3)] {
+        Document { id, owner, shared_with }.insert(&mut db).await.unwrap();
+    }
+    let owned = || Document::select().where_bind("owner = ?", 1);
+    let shared = || Document::select().where_bind("shared_with = ?", 1);
+
+    let documents = owned().union(shared()).order_asc("id").fetch_all(&mut db).await.unwrap();
+    assert_eq!(ids(&documents), vec![1, 2, 3]);
+
+    assert_eq!(owned().union_all(shared()).count(&mut db).await.unwrap(), 4);
+
+    let documents = owned().intersect(shared()).fetch_all(&mut db).await.unwrap();
+    assert_eq!(ids(&documents), vec![3]);
+
+    let documents = owned().except(shared()).fetch_all(&mut db).await.unwrap();
+    assert_eq!(ids(&documents), vec![1]);
+
+    // Filters, ordering and limits added afterwards apply to the combined models, with arguments in order.
+    let documents = owned()
+        .union(shared())
+        .where_bind("id > ?", 1)
+        .order_desc("id")
+        .limit(1)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(ids(&documents), vec![3]);
+
+    // Either side can have its own ordering and limit, and compound queries can be combined again.
+    let documents = owned()
+        .order_desc("id")
+        .limit(1)
+        .union(Document::select().where_bind("owner = ?", 3))
+        .union(Document::select().where_in("id", vec![5]))
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(ids(&documents), vec![3, 4, 5]);
+}
@@ -0,0 +1,69 @@
+use ormlite::model::*;
+use ormlite::Connection;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+pub struct Person {
+    id: i32,
+    name: String,
+    age: i32,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    Person {
+        id: 1,
+        name: "alice".to_string(),
+        age: 30,
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+
+    let query = Person::select()
+        .where_bind("age > ?", 18)
+        .where_in("id", vec![1, 2])
+        .order_asc("id")
+        .limit(5);
+    let sql = r#"SELECT "person".* FROM "person"  WHERE age > ? AND id IN (?, ?) ORDER BY id ASC LIMIT 5"#;
+    assert_eq!(query.to_sql().unwrap(), sql);
+    assert_eq!(query.to_string(), format!("{sql}\n-- [18, 1, 2]"));
+    assert_eq!(
+        format!("{query:?}"),
+        format!("SelectQueryBuilder {{ sql: Ok({sql:?}), arguments: [18, 1, 2] }}")
+    );
+    // The builder is kept, so it can still run.
+    assert_eq!(query.fetch_all(&mut db).await.unwrap().len(), 1);
+
+    let update = Person::update_where().set("name", "bob").where_bind("id = ?", 1);
+    assert_eq!(
+        update.to_string(),
+        "UPDATE \"person\" SET \"name\" = ? WHERE id = ?\n-- [\"bob\", 1]"
+    );
+    assert_eq!(update.execute(&mut db).await.unwrap(), 1);
+
+    let delete = Person::delete_where().where_bind("name = ?", "bob");
+    assert_eq!(delete.to_sql().unwrap(), "DELETE FROM \"person\" WHERE name = ?");
+    assert_eq!(delete.execute(&mut db).await.unwrap(), 1);
+
+    let projection = Person::select().where_bind("age > ?", 1).select_as::<Person>();
+    assert_eq!(
+        projection.to_sql().unwrap(),
+        r#"SELECT "person"."id", "person"."name", "person"."age" FROM "person"  WHERE age > ?"#
+    );
+
+    // Queries that can't be built show the error.
+    let locked = Person::select().for_update();
+    assert!(locked.to_sql().is_err());
+    assert!(locked.to_string().starts_with("-- SQLite doesn't support row locks"));
+}
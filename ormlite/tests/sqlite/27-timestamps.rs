@@ -0,0 +1,119 @@
+use ormlite::model::*;
+use ormlite::Connection;
+
+#[path = "../setup.rs"]
+mod setup;
+
+const OLD: &str = "2000-01-01 00:00:00";
+
+#[derive(Model, Debug)]
+#[ormlite(insert = "InsertPost")]
+pub struct Post {
+    id: i32,
+    title: String,
+    #[ormlite(created_at)]
+    created_at: String,
+    #[ormlite(updated_at)]
+    updated_at: String,
+}
+
+async fn age(db: &mut ormlite::sqlite::SqliteConnection) {
+    ormlite::query(&format!("UPDATE post SET created_at = '{OLD}', updated_at = '{OLD}'"))
+        .execute(db)
+        .await
+        .unwrap();
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        assert!(!sql.contains("created_at") || sql.contains("DEFAULT CURRENT_TIMESTAMP"));
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    // Every kind of insert sets both timestamps, whatever the fields hold.
+    let post = Post {
+        id: 1,
+        title: "first".to_string(),
+        created_at: OLD.to_string(),
+        updated_at: String::new(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert_ne!(post.created_at, OLD);
+    assert!(!post.updated_at.is_empty());
+
+    let post = InsertPost {
+        title: "second".to_string(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert!(!post.created_at.is_empty() && !post.updated_at.is_empty());
+
+    let post = Post::builder().id(3).title("third").insert(&mut db).await.unwrap();
+    assert!(!post.created_at.is_empty() && !post.updated_at.is_empty());
+
+    let posts = Post::insert_many(
+        vec![
+            Post {
+                id: 4,
+                title: "fourth".to_string(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            },
+            Post {
+                id: 5,
+                title: "fifth".to_string(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            },
+        ],
+        &mut db,
+    )
+    .await
+    .unwrap();
+    assert!(posts.iter().all(|p| !p.created_at.is_empty() && !p.updated_at.is_empty()));
+
+    // Updates keep created_at, and set updated_at.
+    age(&mut db).await;
+    let mut post = Post::fetch_one(1, &mut db).await.unwrap();
+    post.title = "first, edited".to_string();
+    post.created_at = String::new();
+    let post = post.update_all_fields(&mut db).await.unwrap();
+    assert_eq!(post.created_at, OLD);
+    assert_ne!(post.updated_at, OLD);
+
+    age(&mut db).await;
+    let post = Post::fetch_one(2, &mut db).await.unwrap();
+    let post = post.update_partial().title("second, edited").update(&mut db).await.unwrap();
+    assert_eq!(post.created_at, OLD);
+    assert_ne!(post.updated_at, OLD);
+
+    age(&mut db).await;
+    let updated = Post::update_where()
+        .set("title", "bulk")
+        .where_bind("id > ?", 3)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(updated.len(), 2);
+    assert!(updated.iter().all(|p| p.created_at == OLD && p.updated_at != OLD));
+
+    // Setting updated_at explicitly takes precedence.
+    let post = Post::fetch_one(3, &mut db).await.unwrap();
+    let post = post.update_partial().updated_at(OLD.to_string()).update(&mut db).await.unwrap();
+    assert_eq!(post.updated_at, OLD);
+    let updated = Post::update_where()
+        .set("updated_at", "1999-01-01 00:00:00")
+        .where_bind("id = ?", 3)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(updated[0].updated_at, "1999-01-01 00:00:00");
+}
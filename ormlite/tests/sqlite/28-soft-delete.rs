@@ -0,0 +1,195 @@
+use ormlite::Connection;
+use ormlite::model::*;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+#[ormlite(soft_delete = "deleted_at")]
+pub struct Post {
+    id: i32,
+    title: String,
+    deleted_at: Option<String>,
+}
+
+#[derive(Model, Debug)]
+pub struct Comment {
+    id: i32,
+    #[ormlite(column = "post_id")]
+    post: Join<Post>,
+}
+
+#[derive(Model, Debug)]
+pub struct Tag {
+    id: i32,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    for (id, title) in [(1, "one"), (2, "two"), (3, "three"), (4, "four")] {
+        Post {
+            id,
+            title: title.to_string(),
+            deleted_at: None,
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+
+    // Deleting marks the row, and the model is no longer selected.
+    let post = Post::fetch_one(1, &mut db).await.unwrap();
+    post.delete(&mut db).await.unwrap();
+    assert!(Post::fetch_one(1, &mut db).await.is_err());
+    assert_eq!(Post::delete_by_id(2, &mut db).await.unwrap(), 1);
+    assert_eq!(Post::delete_by_id(2, &mut db).await.unwrap(), 0);
+    let deleted = Post::delete_where()
+        .where_bind("id = ?", 3)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert!(deleted[0].deleted_at.is_some());
+
+    let posts = Post::select().fetch_all(&mut db).await.unwrap();
+    assert_eq!(posts.iter().map(|p| p.id).collect::<Vec<_>>(), [4]);
+    assert_eq!(Post::select().count(&mut db).await.unwrap(), 1);
+    assert_eq!(Post::select().with_deleted().count(&mut db).await.unwrap(), 4);
+    let deleted = Post::select()
+        .only_deleted()
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(deleted.iter().map(|p| p.id).collect::<Vec<_>>(), [1, 2, 3]);
+    let (rows,): (i64,) = ormlite::query_as("SELECT COUNT(*) FROM post")
+        .fetch_one(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(rows, 4);
+
+    // The scope applies to all of a condition with an OR, in selects and in deletes.
+    let posts = Post::select()
+        .where_("id = ? OR title = ?")
+        .bind(1)
+        .bind("four")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(posts.iter().map(|p| p.id).collect::<Vec<_>>(), [4]);
+    let posts = Post::select()
+        .only_deleted()
+        .where_("id = ? OR id = ?")
+        .bind(3)
+        .bind(4)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(posts.iter().map(|p| p.id).collect::<Vec<_>>(), [3]);
+    ormlite::query("UPDATE post SET deleted_at = '2000-01-01 00:00:00' WHERE id = 2")
+        .execute(&mut db)
+        .await
+        .unwrap();
+    let count = Post::delete_where()
+        .where_("id = ? OR id = ?")
+        .bind(2)
+        .bind(99)
+        .execute(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+    let post = Post::select().with_deleted().where_bind("id = ?", 2).fetch_one(&mut db).await.unwrap();
+    assert_eq!(post.deleted_at.as_deref(), Some("2000-01-01 00:00:00"));
+
+    // The scope applies inside subqueries and compound queries.
+    let posts = Post::select()
+        .with_deleted()
+        .where_in_subquery("id", Post::select().clear_select().select("id"))
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 1);
+    let posts = Post::select()
+        .where_bind("id = ?", 4)
+        .union(Post::select().only_deleted().where_bind("id = ?", 1))
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 2);
+    assert!(
+        Post::select()
+            .to_sql()
+            .unwrap()
+            .contains("\"post\".\"deleted_at\" IS NULL")
+    );
+
+    // Restoring clears the column.
+    let post = deleted.into_iter().next().unwrap();
+    let post = post.restore(&mut db).await.unwrap();
+    assert!(post.deleted_at.is_none());
+    assert_eq!(Post::fetch_one(1, &mut db).await.unwrap().title, "one");
+
+    // Soft deleted models aren't joined, whether by a LEFT JOIN, load_all or load.
+    for (id, post_id) in [(1, 2), (2, 4)] {
+        Comment {
+            id,
+            post: Join::new_with_id(post_id),
+        }
+        .insert(&mut db)
+        .await
+        .unwrap();
+    }
+    let mut comments = Comment::select()
+        .join(Comment::post())
+        .order_asc("id")
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert!(!comments[0].post.loaded());
+    assert_eq!(comments[1].post.title, "four");
+    assert!(
+        Comment::select()
+            .join(Comment::post())
+            .to_sql()
+            .unwrap()
+            .contains("\"post\".\"deleted_at\" IS NULL")
+    );
+    let mut loaded = Comment::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    loaded.load_all(Comment::post(), &mut db).await.unwrap();
+    assert!(!loaded[0].post.loaded());
+    assert!(loaded[1].post.loaded());
+    assert!(comments[0].post.load(&mut db).await.is_err());
+
+    // Models without soft delete are deleted, and can't be restored.
+    Tag {
+        id: 1,
+        name: "rust".to_string(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert!(Tag::delete_where().to_sql().unwrap().starts_with("DELETE"));
+    let tag = Tag::fetch_one(1, &mut db).await.unwrap();
+    assert!(
+        Tag {
+            id: 1,
+            name: "rust".to_string(),
+        }
+        .restore(&mut db)
+        .await
+        .is_err()
+    );
+    tag.delete(&mut db).await.unwrap();
+    assert!(Tag::select().fetch_optional(&mut db).await.unwrap().is_none());
+    // There are no deleted models to include or select.
+    assert_eq!(Tag::select().with_deleted().count(&mut db).await.unwrap(), 0);
+    assert!(Tag::select().only_deleted().fetch_all(&mut db).await.is_err());
+}
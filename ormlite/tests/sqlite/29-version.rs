@@ -0,0 +1,67 @@
+use ormlite::model::*;
+use ormlite::Connection;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug, Clone)]
+#[ormlite(insert = "InsertDocument")]
+pub struct Document {
+    id: i32,
+    body: String,
+    #[ormlite(version)]
+    version: i32,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    let doc = InsertDocument {
+        body: "draft".to_string(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert_eq!(doc.version, 0);
+
+    // Each update checks the version it read, and increments it.
+    let mut first = doc.clone();
+    first.body = "first".to_string();
+    let first = first.update_all_fields(&mut db).await.unwrap();
+    assert_eq!(first.version, 1);
+
+    let mut stale = doc.clone();
+    stale.body = "stale".to_string();
+    let err = stale.update_all_fields(&mut db).await.unwrap_err();
+    assert!(matches!(err, ormlite::Error::StaleData));
+    let err = doc.update_partial().body("stale".to_string()).update(&mut db).await.unwrap_err();
+    assert!(matches!(err, ormlite::Error::StaleData));
+
+    let second = first.update_partial().body("second".to_string()).update(&mut db).await.unwrap();
+    assert_eq!(second.version, 2);
+    let err = first.update_partial().body("stale".to_string()).update(&mut db).await.unwrap_err();
+    assert!(matches!(err, ormlite::Error::StaleData));
+
+    // Bulk updates increment the version too, so models read before them are stale.
+    let updated = Document::update_where()
+        .set("body", "bulk")
+        .where_bind("id = ?", second.id)
+        .fetch_all(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(updated[0].version, 3);
+    assert!(matches!(
+        second.update_all_fields(&mut db).await.unwrap_err(),
+        ormlite::Error::StaleData
+    ));
+
+    let current = Document::fetch_one(doc.id, &mut db).await.unwrap();
+    assert_eq!((current.body.as_str(), current.version), ("bulk", 3));
+}
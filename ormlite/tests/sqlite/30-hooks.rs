@@ -0,0 +1,172 @@
+use ormlite::model::*;
+use ormlite::sqlite::{Sqlite, SqliteConnection};
+use ormlite::{BoxFuture, Connection};
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+#[ormlite(hooks)]
+pub struct User {
+    id: i32,
+    email: String,
+    slug: String,
+}
+
+#[derive(Model, Debug)]
+pub struct Audit {
+    id: i32,
+    action: String,
+}
+
+async fn audit(conn: &mut SqliteConnection, action: String) -> ormlite::Result<()> {
+    ormlite::query("INSERT INTO audit (action) VALUES (?)")
+        .bind(action)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+impl ModelHooks<Sqlite> for User {
+    fn before_insert<'a>(&'a mut self, _conn: &'a mut SqliteConnection) -> BoxFuture<'a, ormlite::Result<()>> {
+        Box::pin(async move {
+            if self.email.is_empty() {
+                return Err(ormlite::Error::OrmliteError("email is required".to_string()));
+            }
+            self.email = self.email.to_lowercase();
+            self.slug = self.email.split('@').next().unwrap().to_string();
+            Ok(())
+        })
+    }
+
+    fn after_insert<'a>(&'a mut self, conn: &'a mut SqliteConnection) -> BoxFuture<'a, ormlite::Result<()>> {
+        Box::pin(async move { audit(conn, format!("insert {}", self.id)).await })
+    }
+
+    fn before_update<'a>(&'a mut self, conn: &'a mut SqliteConnection) -> BoxFuture<'a, ormlite::Result<()>> {
+        Box::pin(async move {
+            self.email = self.email.to_lowercase();
+            audit(conn, format!("update {}", self.id)).await
+        })
+    }
+
+    fn before_delete<'a>(&'a mut self, conn: &'a mut SqliteConnection) -> BoxFuture<'a, ormlite::Result<()>> {
+        Box::pin(async move { audit(conn, format!("delete {}", self.id)).await })
+    }
+
+    fn before_insert_partial<'a, 'b>(
+        builder: &'a mut UserBuilder<'b>,
+        _conn: &'a mut SqliteConnection,
+    ) -> BoxFuture<'a, ormlite::Result<()>> {
+        Box::pin(async move {
+            if let Some(email) = &mut builder.email {
+                *email = email.to_lowercase();
+                builder.slug = Some(email.split('@').next().unwrap().to_string());
+            }
+            Ok(())
+        })
+    }
+
+    fn before_update_partial<'a, 'b>(
+        builder: &'a mut UserBuilder<'b>,
+        _conn: &'a mut SqliteConnection,
+    ) -> BoxFuture<'a, ormlite::Result<()>> {
+        Box::pin(async move {
+            if let Some(email) = &mut builder.email {
+                *email = email.to_lowercase();
+            }
+            Ok(())
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    let user = User {
+        id: 1,
+        email: "Alice@Example.com".to_string(),
+        slug: String::new(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+    assert_eq!(
+        (user.email.as_str(), user.slug.as_str()),
+        ("alice@example.com", "alice")
+    );
+
+    // A hook error aborts the insert.
+    let err = User {
+        id: 2,
+        email: String::new(),
+        slug: String::new(),
+    }
+    .insert(&mut db)
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "email is required");
+    assert!(User::fetch_one(2, &mut db).await.is_err());
+
+    let users = User::insert_many(
+        vec![User {
+            id: 3,
+            email: "Bob@Example.com".to_string(),
+            slug: String::new(),
+        }],
+        &mut db,
+    )
+    .await
+    .unwrap();
+    assert_eq!(users[0].slug, "bob");
+
+    let user = User::builder()
+        .id(4)
+        .email("Carol@Example.com")
+        .insert(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(
+        (user.email.as_str(), user.slug.as_str()),
+        ("carol@example.com", "carol")
+    );
+
+    let mut user = User::fetch_one(1, &mut db).await.unwrap();
+    user.email = "ALICE@example.com".to_string();
+    let user = user.update_all_fields(&mut db).await.unwrap();
+    assert_eq!(user.email, "alice@example.com");
+    let user = user
+        .update_partial()
+        .email("Alice@New.com")
+        .update(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(user.email, "alice@new.com");
+
+    User::fetch_one(3, &mut db)
+        .await
+        .unwrap()
+        .delete(&mut db)
+        .await
+        .unwrap();
+
+    // Hooks run in the query's transaction, so when the query fails, their audit rows are rolled back.
+    let missing = || User {
+        id: 99,
+        email: "nobody@example.com".to_string(),
+        slug: "nobody".to_string(),
+    };
+    assert!(missing().update_all_fields(&mut db).await.is_err());
+    assert!(missing().delete(&mut db).await.is_err());
+
+    let actions = Audit::select().order_asc("id").fetch_all(&mut db).await.unwrap();
+    let actions = actions.iter().map(|a| a.action.as_str()).collect::<Vec<_>>();
+    assert_eq!(actions, ["insert 1", "insert 3", "insert 4", "update 1", "delete 3"]);
+}
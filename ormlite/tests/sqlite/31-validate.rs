@@ -0,0 +1,106 @@
+use ormlite::Connection;
+use ormlite::model::*;
+
+#[path = "../setup.rs"]
+mod setup;
+
+#[derive(Model, Debug)]
+#[ormlite(insert = "InsertUser")]
+pub struct User {
+    id: i32,
+    #[ormlite(validate(length(max = 20), regex = "^[^@]+@[^@]+$"))]
+    email: String,
+    #[ormlite(validate(length(min = 2)))]
+    nickname: Option<String>,
+}
+
+fn field(err: ormlite::Error) -> &'static str {
+    match err {
+        ormlite::Error::Validation { field, .. } => field,
+        err => panic!("expected a validation error, got {err}"),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let mut db = ormlite::sqlite::SqliteConnection::connect(":memory:").await.unwrap();
+    let migration = setup::migrate_self(&[file!()]);
+    for s in migration.statements {
+        let sql = ormlite_core::schema::statement_to_sql(&s, sql::Dialect::Sqlite);
+        if sql.contains("CREATE TABLE") {
+            assert!(sql.contains(r#""email" character varying(20) NOT NULL"#));
+        }
+        ormlite::query(&sql).execute(&mut db).await.unwrap();
+    }
+
+    let user = User {
+        id: 1,
+        email: "alice@example.com".to_string(),
+        nickname: None,
+    }
+    .insert(&mut db)
+    .await
+    .unwrap();
+
+    // Every write path checks the fields, and nothing is written when one is invalid.
+    let err = User {
+        id: 2,
+        email: "not an email".to_string(),
+        nickname: None,
+    }
+    .insert(&mut db)
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "email must match the pattern `^[^@]+@[^@]+$`");
+    let err = InsertUser {
+        email: "a-very-long-address@example.com".to_string(),
+        nickname: None,
+    }
+    .insert(&mut db)
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "email must have a length of at most 20, but has 31");
+    let err = User::insert_many(
+        vec![User {
+            id: 3,
+            email: "bob@example.com".to_string(),
+            nickname: Some("b".to_string()),
+        }],
+        &mut db,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(field(err), "nickname");
+    let err = User::builder().id(4).email("carol").insert(&mut db).await.unwrap_err();
+    assert_eq!(field(err), "email");
+    assert_eq!(User::select().count(&mut db).await.unwrap(), 1);
+
+    let err = user
+        .update_partial()
+        .nickname(Some("a".to_string()))
+        .update(&mut db)
+        .await
+        .unwrap_err();
+    assert_eq!(field(err), "nickname");
+    let mut invalid = User::fetch_one(1, &mut db).await.unwrap();
+    invalid.email = "alice".to_string();
+    assert_eq!(field(invalid.update_all_fields(&mut db).await.unwrap_err()), "email");
+    let user = user
+        .update_partial()
+        .nickname(Some("al".to_string()))
+        .update(&mut db)
+        .await
+        .unwrap();
+    assert_eq!(user.nickname.as_deref(), Some("al"));
+
+    let err = User::builder().id(5).email("dave").nickname(None).build().unwrap_err();
+    assert_eq!(field(err), "email");
+    let built = User::builder()
+        .id(5)
+        .email("dave@example.com")
+        .nickname(None)
+        .build()
+        .unwrap();
+    assert_eq!(built.email, "dave@example.com");
+}